@@ -16,7 +16,7 @@ pub fn find_attacked_bridges(cells: &HexCells, coords: Coords) -> Vec<Coords> {
     };
 
     // Essentially, the following code will search for the pattern [Some(search_color), None, Some(search_color)]
-    // in the colors of the neighbors of `coords`. To find bridges that span the
+    // in the colors of the neighbors of `coords`.
 
     // Bridges may cross the end of the get_neighbors-iterator (e.g. the last element + the first two elements).
     // For this reason we extend the array of neighbors by the first two neighbors.
@@ -27,16 +27,12 @@ pub fn find_attacked_bridges(cells: &HexCells, coords: Coords) -> Vec<Coords> {
     neighbors[neighbor_count] = neighbors[0];
     neighbors[neighbor_count + 1] = neighbors[1];
 
-    println!("Center index {:?}", center_index);
-    println!("Neighbors: {:?}", neighbors);
-
     let mut result = vec![];
     // `state` describes how many fields of the pattern [Some(search_color), None, Some(search_color)] we've already found.
     let mut state = FindAttackedBridgesState::Found0;
 
     for (i, neighbor) in neighbors.iter().enumerate() {
         let color = cells.get_color_at_index(*neighbor);
-        println!("Iter {} {} {:?}", i, neighbor, color);
         match state {
             FindAttackedBridgesState::Found0 => {
                 if color == Some(search_color) {
@@ -102,7 +98,7 @@ mod tests {
     #[test]
     fn test_coords_without_neighbors() {
         let mut cells = empty_cells_with_colored_edges();
-        let _ = cells.set_color_at_coords(CENTER, Color::White);
+        cells.set_color_at_coords(CENTER, Color::White);
         assert_eq!(find_attacked_bridges(&cells, CENTER), vec![]);
     }
 
@@ -114,9 +110,9 @@ mod tests {
         //   3\.  .  ○  .  .\3
         //    4\.  .  ●  .  .\4
         let mut cells = empty_cells_with_colored_edges();
-        let _ = cells.set_color_at_coords(Coords { row: 1, column: 3 }, Color::Black);
-        let _ = cells.set_color_at_coords(Coords { row: 3, column: 2 }, Color::Black);
-        let _ = cells.set_color_at_coords(CENTER, Color::White);
+        cells.set_color_at_coords(Coords { row: 1, column: 3 }, Color::Black);
+        cells.set_color_at_coords(Coords { row: 3, column: 2 }, Color::Black);
+        cells.set_color_at_coords(CENTER, Color::White);
 
         assert_eq!(
             find_attacked_bridges(&cells, CENTER),
@@ -131,10 +127,10 @@ mod tests {
         //  2\.  .  ●  .  .\2
         //   3\.  ●  ○  ●  .\3
         let mut cells = empty_cells_with_colored_edges();
-        let _ = cells.set_color_at_coords(Coords { row: 2, column: 1 }, Color::Black);
-        let _ = cells.set_color_at_coords(Coords { row: 1, column: 2 }, Color::Black);
-        let _ = cells.set_color_at_coords(Coords { row: 2, column: 3 }, Color::Black);
-        let _ = cells.set_color_at_coords(CENTER, Color::White);
+        cells.set_color_at_coords(Coords { row: 2, column: 1 }, Color::Black);
+        cells.set_color_at_coords(Coords { row: 1, column: 2 }, Color::Black);
+        cells.set_color_at_coords(Coords { row: 2, column: 3 }, Color::Black);
+        cells.set_color_at_coords(CENTER, Color::White);
 
         assert_eq!(
             find_attacked_bridges(&cells, CENTER),
@@ -149,10 +145,10 @@ mod tests {
         //  2\.  .  ○  .  .\2
         //   3\.  ●  ○  ●  .\3
         let mut cells = empty_cells_with_colored_edges();
-        let _ = cells.set_color_at_coords(Coords { row: 2, column: 1 }, Color::Black);
-        let _ = cells.set_color_at_coords(Coords { row: 1, column: 2 }, Color::White);
-        let _ = cells.set_color_at_coords(Coords { row: 2, column: 3 }, Color::Black);
-        let _ = cells.set_color_at_coords(CENTER, Color::White);
+        cells.set_color_at_coords(Coords { row: 2, column: 1 }, Color::Black);
+        cells.set_color_at_coords(Coords { row: 1, column: 2 }, Color::White);
+        cells.set_color_at_coords(Coords { row: 2, column: 3 }, Color::Black);
+        cells.set_color_at_coords(CENTER, Color::White);
 
         assert_eq!(find_attacked_bridges(&cells, CENTER), vec![]);
     }
@@ -165,10 +161,10 @@ mod tests {
         //   3\.  ●  ○  .  .\3
         //    4\.  .  ●  .  .\4
         let mut cells = empty_cells_with_colored_edges();
-        let _ = cells.set_color_at_coords(Coords { row: 2, column: 1 }, Color::Black);
-        let _ = cells.set_color_at_coords(Coords { row: 1, column: 3 }, Color::Black);
-        let _ = cells.set_color_at_coords(Coords { row: 3, column: 2 }, Color::Black);
-        let _ = cells.set_color_at_coords(CENTER, Color::White);
+        cells.set_color_at_coords(Coords { row: 2, column: 1 }, Color::Black);
+        cells.set_color_at_coords(Coords { row: 1, column: 3 }, Color::Black);
+        cells.set_color_at_coords(Coords { row: 3, column: 2 }, Color::Black);
+        cells.set_color_at_coords(CENTER, Color::White);
 
         assert_eq!(
             find_attacked_bridges(&cells, CENTER),
@@ -188,9 +184,9 @@ mod tests {
         //   3\.  .  ○  .  .\3
         //    4\.  ●  .  .  .\4
         let mut cells = empty_cells_with_colored_edges();
-        let _ = cells.set_color_at_coords(Coords { row: 3, column: 1 }, Color::Black);
-        let _ = cells.set_color_at_coords(Coords { row: 1, column: 2 }, Color::Black);
-        let _ = cells.set_color_at_coords(CENTER, Color::White);
+        cells.set_color_at_coords(Coords { row: 3, column: 1 }, Color::Black);
+        cells.set_color_at_coords(Coords { row: 1, column: 2 }, Color::Black);
+        cells.set_color_at_coords(CENTER, Color::White);
 
         assert_eq!(
             find_attacked_bridges(&cells, CENTER),
@@ -205,8 +201,8 @@ mod tests {
         //  2\●  .  .  .  .\2
         let mut cells = empty_cells_with_colored_edges();
         let attacked_coords = Coords { row: 0, column: 1 };
-        let _ = cells.set_color_at_coords(Coords { row: 1, column: 0 }, Color::Black);
-        let _ = cells.set_color_at_coords(attacked_coords, Color::White);
+        cells.set_color_at_coords(Coords { row: 1, column: 0 }, Color::Black);
+        cells.set_color_at_coords(attacked_coords, Color::White);
 
         assert_eq!(
             find_attacked_bridges(&cells, attacked_coords),
@@ -221,8 +217,8 @@ mod tests {
         //  2\●  .  .  .  .\2
         let mut cells = empty_cells_with_colored_edges();
         let attacked_coords = Coords { row: 0, column: 0 };
-        let _ = cells.set_color_at_coords(Coords { row: 1, column: 0 }, Color::Black);
-        let _ = cells.set_color_at_coords(attacked_coords, Color::White);
+        cells.set_color_at_coords(Coords { row: 1, column: 0 }, Color::Black);
+        cells.set_color_at_coords(attacked_coords, Color::White);
 
         assert_eq!(
             find_attacked_bridges(&cells, attacked_coords),
@@ -237,8 +233,8 @@ mod tests {
         //  2\.  .  ●  .  .\2
         let mut cells = empty_cells_with_colored_edges();
         let attacked_coords = Coords { row: 0, column: 2 };
-        let _ = cells.set_color_at_coords(Coords { row: 1, column: 2 }, Color::Black);
-        let _ = cells.set_color_at_coords(attacked_coords, Color::White);
+        cells.set_color_at_coords(Coords { row: 1, column: 2 }, Color::Black);
+        cells.set_color_at_coords(attacked_coords, Color::White);
 
         assert_eq!(
             find_attacked_bridges(&cells, attacked_coords),
@@ -254,8 +250,8 @@ mod tests {
         //   3\○  ●  .  .  .\3
         let mut cells = empty_cells_with_colored_edges();
         let attacked_coords = Coords { row: 2, column: 0 };
-        let _ = cells.set_color_at_coords(Coords { row: 2, column: 1 }, Color::Black);
-        let _ = cells.set_color_at_coords(attacked_coords, Color::White);
+        cells.set_color_at_coords(Coords { row: 2, column: 1 }, Color::Black);
+        cells.set_color_at_coords(attacked_coords, Color::White);
 
         assert_eq!(find_attacked_bridges(&cells, attacked_coords), vec![]);
     }
@@ -0,0 +1,418 @@
+use crate::board::Board;
+use crate::color::Color;
+use crate::coords::{CoordValue, Coords};
+use crate::errors::LoadError;
+use crate::game::{Game, GameResult, WinReason};
+use std::io;
+
+// Layout of encoded boards: [size, cells...]
+// Layout of encoded games:  [size, status, cells...]
+// The status byte contains the current player in bits 0-1. For finished games, bits 2-3 contain the winner and bits 4-5
+// the reason (see `encode_reason`); these bits are zero for ongoing games.
+// Cells are stored row by row with 2 bits per cell, starting with the least significant bits of each byte.
+const EMPTY: u8 = 0;
+const BLACK: u8 = 1;
+const WHITE: u8 = 2;
+
+pub fn encode_board(board: &Board) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + cell_byte_count(board.size()));
+    bytes.push(board.size());
+    encode_cells(board, &mut bytes);
+    bytes
+}
+
+pub fn decode_board(bytes: &[u8]) -> Result<Board, LoadError> {
+    let size = *bytes.first().ok_or(LoadError::InvalidLength(1, 0))?;
+    check_length(bytes, 1 + cell_byte_count(size))?;
+    Ok(Board::from_stone_matrix(decode_cells(size, &bytes[1..])?)?)
+}
+
+pub fn encode_game(game: &Game) -> Vec<u8> {
+    let board = game.get_board();
+    let mut bytes = Vec::with_capacity(2 + cell_byte_count(board.size()));
+    bytes.push(board.size());
+    bytes.push(encode_status(game));
+    encode_cells(board, &mut bytes);
+    bytes
+}
+
+pub fn decode_game(bytes: &[u8]) -> Result<Game, LoadError> {
+    let size = *bytes.first().ok_or(LoadError::InvalidLength(2, 0))?;
+    check_length(bytes, encoded_game_length(size))?;
+    let (current_player, result) = decode_status(bytes[1])?;
+    let stones = decode_cells(size, &bytes[2..])?;
+    let result = match result {
+        None => return Ok(Game::load(stones, current_player)?),
+        Some(result) => result,
+    };
+
+    // As in the JSON format, the player who was on move when the game ended is not known. Use the loser.
+    let mut game = Game::load(stones, Some(result.loser()))?;
+    match game.get_result() {
+        Some(actual) if actual != result => Err(LoadError::InvalidResult(format!(
+            "Stored result \"{}\" does not match the board \"{}\"",
+            result, actual
+        ))),
+        Some(_) => Ok(game),
+        None if result.reason == WinReason::Connection => Err(LoadError::InvalidResult(format!(
+            "Stored result \"{}\" but the board has no connection",
+            result
+        ))),
+        None => {
+            game.finish(result);
+            Ok(game)
+        }
+    }
+}
+
+pub fn encoded_game_length(size: CoordValue) -> usize {
+    2 + cell_byte_count(size)
+}
+
+fn cell_byte_count(size: CoordValue) -> usize {
+    let cell_count = (size as usize) * (size as usize);
+    cell_count.div_ceil(4)
+}
+
+fn check_length(bytes: &[u8], expected: usize) -> Result<(), LoadError> {
+    if bytes.len() == expected {
+        Ok(())
+    } else {
+        Err(LoadError::InvalidLength(expected, bytes.len()))
+    }
+}
+
+fn encode_cells(board: &Board, bytes: &mut Vec<u8>) {
+    let start = bytes.len();
+    bytes.resize(start + cell_byte_count(board.size()), 0);
+
+    for row in 0..board.size() {
+        for column in 0..board.size() {
+            let index = (row as usize) * (board.size() as usize) + (column as usize);
+            let code = encode_color(board.get_color(Coords { row, column }));
+            bytes[start + index / 4] |= code << (2 * (index % 4));
+        }
+    }
+}
+
+fn decode_cells(size: CoordValue, bytes: &[u8]) -> Result<Vec<Vec<Option<Color>>>, LoadError> {
+    // Reject padding bits so that each position has exactly one encoding.
+    let used_bits = 2 * ((size as usize) * (size as usize) % 4);
+    if let Some(last) = bytes.last() {
+        if used_bits > 0 && last >> used_bits != 0 {
+            return Err(LoadError::InvalidPadding(*last));
+        }
+    }
+
+    (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|column| {
+                    let index = (row as usize) * (size as usize) + (column as usize);
+                    let code = (bytes[index / 4] >> (2 * (index % 4))) & 0b11;
+                    decode_color(code, Some(Coords { row, column }))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn encode_status(game: &Game) -> u8 {
+    let result = match game.get_result() {
+        Some(result) => {
+            (encode_color(Some(result.winner)) << 2) | (encode_reason(result.reason) << 4)
+        }
+        None => 0,
+    };
+    encode_color(game.get_current_player()) | result
+}
+
+fn decode_status(status: u8) -> Result<(Option<Color>, Option<GameResult>), LoadError> {
+    let current_player = decode_color(status & 0b11, None)?;
+    let winner = decode_color((status >> 2) & 0b11, None)?;
+    let reason = (status >> 4) & 0b11;
+    match (current_player, winner) {
+        _ if status >> 6 != 0 => Err(invalid_status(status)),
+        (None, Some(winner)) => Ok((None, Some(GameResult::new(winner, decode_reason(reason))))),
+        (_, None) if reason == 0 => Ok((current_player, None)),
+        _ => Err(invalid_status(status)),
+    }
+}
+
+fn invalid_status(status: u8) -> LoadError {
+    LoadError::InvalidResult(format!("Invalid status byte {}", status))
+}
+
+fn encode_reason(reason: WinReason) -> u8 {
+    match reason {
+        WinReason::Connection => 0,
+        WinReason::Resignation => 1,
+        WinReason::Timeout => 2,
+        WinReason::Forfeit => 3,
+    }
+}
+
+fn decode_reason(code: u8) -> WinReason {
+    match code {
+        0 => WinReason::Connection,
+        1 => WinReason::Resignation,
+        2 => WinReason::Timeout,
+        _ => WinReason::Forfeit,
+    }
+}
+
+fn encode_color(color: Option<Color>) -> u8 {
+    match color {
+        None => EMPTY,
+        Some(Color::Black) => BLACK,
+        Some(Color::White) => WHITE,
+    }
+}
+
+fn decode_color(code: u8, coords: Option<Coords>) -> Result<Option<Color>, LoadError> {
+    match code {
+        EMPTY => Ok(None),
+        BLACK => Ok(Some(Color::Black)),
+        WHITE => Ok(Some(Color::White)),
+        _ => Err(LoadError::InvalidColor(code, coords)),
+    }
+}
+
+/// Writes many positions to a stream in the compact binary format of `Game::to_bytes`.
+///
+/// Positions are simply concatenated, so files written by `PositionWriter` can be appended to. Use `PositionReader` to read them.
+///
+/// ```
+/// # use hexgame::{Coords, Game, PositionReader, PositionWriter};
+/// let mut game = Game::new(5);
+/// let mut writer = PositionWriter::new(Vec::new());
+/// writer.write(&game).unwrap();
+/// game.play(Coords::new(2, 2)).unwrap();
+/// writer.write(&game).unwrap();
+///
+/// let bytes = writer.into_inner();
+/// let reader = PositionReader::new(bytes.as_slice());
+/// let games: Vec<Game> = reader.collect::<Result<_, _>>().unwrap();
+/// assert_eq!(games.len(), 2);
+/// ```
+pub struct PositionWriter<W: io::Write> {
+    writer: W,
+}
+
+impl<W: io::Write> PositionWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Write the current position of the given game.
+    pub fn write(&mut self, game: &Game) -> io::Result<()> {
+        self.writer.write_all(&encode_game(game))
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads positions written by `PositionWriter` from a stream.
+///
+/// `PositionReader` is an iterator over the positions. Invalid data is reported as `std::io::Error` of kind `InvalidData`
+/// wrapping a `LoadError`.
+pub struct PositionReader<R: io::Read> {
+    reader: R,
+    buffer: Vec<u8>,
+}
+
+impl<R: io::Read> PositionReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: vec![],
+        }
+    }
+
+    /// Read the next position. Return None if the stream ends before the next position.
+    pub fn read(&mut self) -> io::Result<Option<Game>> {
+        let mut size = [0u8];
+        if self.reader.read(&mut size)? == 0 {
+            return Ok(None);
+        }
+
+        self.buffer.clear();
+        self.buffer.resize(encoded_game_length(size[0]), 0);
+        self.buffer[0] = size[0];
+        self.reader.read_exact(&mut self.buffer[1..])?;
+
+        decode_game(&self.buffer)
+            .map(Some)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+impl<R: io::Read> Iterator for PositionReader<R> {
+    type Item = io::Result<Game>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::InvalidBoard;
+
+    #[test]
+    fn test_encode_board() {
+        let mut board = Board::new(3);
+        board.play(Coords::new(0, 1), Color::Black).unwrap();
+        board.play(Coords::new(2, 2), Color::White).unwrap();
+
+        // 9 cells need 3 bytes. b1 is the second cell, c3 the ninth.
+        assert_eq!(encode_board(&board), vec![3, 0b0000_0100, 0, 0b10]);
+    }
+
+    #[test]
+    fn test_board_cycle() {
+        let mut board = Board::new(5);
+        board.play(Coords::new(0, 4), Color::Black).unwrap();
+        board.play(Coords::new(3, 1), Color::White).unwrap();
+        board.play(Coords::new(4, 4), Color::Black).unwrap();
+
+        let decoded_board = decode_board(&encode_board(&board)).unwrap();
+
+        assert_eq!(decoded_board.to_stone_matrix(), board.to_stone_matrix());
+    }
+
+    #[test]
+    fn test_resigned_game_cycle() {
+        let mut game = Game::new(4);
+        game.play(Coords::new(1, 1)).unwrap();
+        game.resign(Color::White).unwrap();
+
+        let bytes = encode_game(&game);
+        let decoded_game = decode_game(&bytes).unwrap();
+
+        // Black (1) wins by resignation (1).
+        assert_eq!(bytes[1], 0b01_01_00);
+        assert_eq!(decoded_game.get_result(), game.get_result());
+        assert_eq!(decoded_game.get_current_player(), None);
+        assert_eq!(
+            decoded_game.get_board().to_stone_matrix(),
+            game.get_board().to_stone_matrix()
+        );
+    }
+
+    #[test]
+    fn test_finished_game_cycle() {
+        let mut game = Game::new(2);
+        game.play(Coords::new(0, 0)).unwrap();
+        game.play(Coords::new(0, 1)).unwrap();
+        game.play(Coords::new(1, 0)).unwrap();
+        let mut timeout = Game::new(2);
+        timeout.lose_on_time(Color::Black).unwrap();
+
+        for game in [game, timeout] {
+            let decoded_game = decode_game(&encode_game(&game)).unwrap();
+            assert_eq!(decoded_game.get_result(), game.get_result());
+        }
+    }
+
+    #[test]
+    fn test_decode_game_with_invalid_result() {
+        // White cannot have won by connection on an empty board.
+        let bytes = [2, 0b00_10_00, 0];
+        assert_eq!(
+            decode_game(&bytes).err().unwrap(),
+            LoadError::InvalidResult(
+                "Stored result \"White wins by connection\" but the board has no connection"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            decode_game(&[2, 0b01_00_00, 0]).err().unwrap(),
+            LoadError::InvalidResult("Invalid status byte 16".to_string())
+        );
+    }
+
+    #[test]
+    fn test_game_cycle() {
+        let mut game = Game::new(4);
+        game.play(Coords::new(1, 1)).unwrap();
+
+        let bytes = encode_game(&game);
+        let decoded_game = decode_game(&bytes).unwrap();
+
+        assert_eq!(bytes.len(), 6);
+        assert_eq!(decoded_game.get_current_player(), Some(Color::White));
+        assert_eq!(
+            decoded_game.get_board().to_stone_matrix(),
+            game.get_board().to_stone_matrix()
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        assert_eq!(
+            decode_board(&[]).err().unwrap(),
+            LoadError::InvalidLength(1, 0)
+        );
+        assert_eq!(
+            decode_board(&[3, 0, 0]).err().unwrap(),
+            LoadError::InvalidLength(4, 3)
+        );
+        assert_eq!(
+            decode_board(&[2, 0b1100_0000]).err().unwrap(),
+            LoadError::InvalidColor(3, Some(Coords::new(1, 1)))
+        );
+        assert_eq!(
+            decode_board(&[3, 0, 0, 0b0000_0100]).err().unwrap(),
+            LoadError::InvalidPadding(0b0000_0100)
+        );
+        assert_eq!(
+            decode_game(&[3, 1, 0, 0, 0b1000_0000]).err().unwrap(),
+            LoadError::InvalidPadding(0b1000_0000)
+        );
+        assert_eq!(
+            decode_board(&[1, 0]).err().unwrap(),
+            LoadError::InvalidBoard(InvalidBoard::SizeOutOfBounds(1, 2, 19))
+        );
+        assert_eq!(
+            decode_game(&[2, 0, 0]).err().unwrap(),
+            LoadError::InvalidBoard(InvalidBoard::NoCurrentPlayer)
+        );
+    }
+
+    #[test]
+    fn test_reader_and_writer() {
+        let mut game = Game::new(3);
+        let mut writer = PositionWriter::new(Vec::new());
+        writer.write(&game).unwrap();
+        game.play(Coords::new(0, 0)).unwrap();
+        writer.write(&game).unwrap();
+
+        let bytes = writer.into_inner();
+        let games: Vec<Game> = PositionReader::new(bytes.as_slice())
+            .collect::<io::Result<_>>()
+            .unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[1].get_current_player(), Some(Color::White));
+    }
+
+    #[test]
+    fn test_reader_with_truncated_data() {
+        let bytes = encode_game(&Game::new(3));
+        let mut reader = PositionReader::new(&bytes[..bytes.len() - 1]);
+
+        let error = reader.read().err().unwrap();
+
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -1,10 +1,20 @@
 use crate::attacked_bridges::find_attacked_bridges;
+use crate::binary::{decode_board, encode_board};
+use crate::bridges::{bridges, broken_bridges, Bridge};
 use crate::color::Color;
 use crate::coords::{CoordValue, Coords};
 use crate::edges::{set_edge_colors, CoordsOrEdge};
-use crate::errors::{InvalidBoard, InvalidMove};
+use crate::errors::{InvalidBoard, InvalidMove, LoadError};
+use crate::format::{render, RenderOptions};
+use crate::frozen_board::FrozenBoard;
+use crate::groups::{group_of, groups, Group};
 use crate::hex_cells::{HexCells, Index};
+use crate::ladders::{ladders, Ladder};
 use crate::neighbors::get_neighbors;
+use crate::solver::{solve, Solution};
+use crate::svg::{board_to_svg, SvgOptions};
+use crate::symmetry::{canonicalize, Symmetry};
+use crate::threats::{threats, winning_moves};
 use crate::union_find::UnionFind;
 use std::iter::Iterator;
 
@@ -52,6 +62,8 @@ pub type StoneMatrix = Vec<Vec<Option<Color>>>;
 ///     5\.  .  .  .  .\5
 ///        a  b  c  d  e
 /// ```
+///
+/// This format can be parsed back into a board via `FromStr`, e.g. `"x . \n . o".parse::<Board>()`.
 #[derive(Clone)]
 pub struct Board {
     cells: HexCells,
@@ -99,6 +111,87 @@ impl Board {
             .collect()
     }
 
+    /// Encode this board in a compact binary format: One byte for the size, followed by 2 bits per cell (row by row).
+    ///
+    /// For example, a 19x19 board needs 92 bytes. Unused bits in the last byte are zero, so each board has exactly one
+    /// encoding. Use `Game::to_bytes` to include the current player.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_board(self)
+    }
+
+    /// Decode a board from the format produced by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LoadError> {
+        decode_board(bytes)
+    }
+
+    /// Render this board in a human-readable format. With default options, this is the same as `to_string`.
+    /// See `RenderOptions` for an example.
+    pub fn render(&self, options: &RenderOptions) -> String {
+        render(self, options)
+    }
+
+    /// Draw this board as an SVG diagram. See `SvgOptions` for labels, markers and arrows.
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        board_to_svg(self, options)
+    }
+
+    /// Return a copy of this board rotated by 180°. The rotated position is equivalent to this one.
+    pub fn rotate_180(&self) -> Board {
+        Symmetry::Rotate180.apply_to_board(self)
+    }
+
+    /// Return a copy of this board reflected at the long diagonal (see `Coords::transpose`) with the colors of all stones swapped.
+    /// The resulting position is equivalent to this one if the player to move is swapped as well.
+    pub fn transpose_swap_colors(&self) -> Board {
+        Symmetry::TransposeSwapColors.apply_to_board(self)
+    }
+
+    /// Return the canonical form of this board, i.e. the lexicographically smallest of all symmetric boards (see `Symmetry`),
+    /// and the symmetry that transforms this board into the canonical form.
+    ///
+    /// Equivalent positions have the same canonical form, so it can be used as key in opening books or transposition tables.
+    /// If the symmetry swaps colors, the player to move must be swapped, too.
+    /// ```
+    /// # use hexgame::{Board, Color, Coords};
+    /// let mut board = Board::new(5);
+    /// board.play(Coords::new(0, 1), Color::Black).unwrap();
+    /// let mut rotated_board = Board::new(5);
+    /// rotated_board.play(Coords::new(4, 3), Color::Black).unwrap();
+    ///
+    /// let (canonical_board, _) = board.canonicalize();
+    /// let (canonical_rotated_board, _) = rotated_board.canonicalize();
+    /// assert_eq!(canonical_board.to_stone_matrix(), canonical_rotated_board.to_stone_matrix());
+    /// ```
+    pub fn canonicalize(&self) -> (Board, Symmetry) {
+        canonicalize(self)
+    }
+
+    /// Return the game-theoretic value of this position with `player` to move and all winning moves.
+    ///
+    /// Only boards up to `MAX_SOLVED_SIZE` are supported; for larger boards, this method returns None. It also returns
+    /// None if the numbers of stones cannot occur with this player to move. Up to 3x3, the first call for each size
+    /// generates a `SolutionTable`, which takes a fraction of a second. Generating the table for 4x4 takes a few seconds
+    /// in release mode and more than a minute in debug mode, so 4x4 boards return None until a table has been
+    /// installed with `SolutionTable::install`, e.g. one written by `hexgame solve`.
+    ///
+    /// ```
+    /// # use hexgame::{Board, Color, Coords};
+    /// let mut board = Board::new(2);
+    /// board.play(Coords::new(0, 0), Color::Black).unwrap();
+    ///
+    /// let solution = board.solve(Color::White).unwrap();
+    /// assert_eq!(solution.winner, Color::White);
+    /// assert_eq!(solution.winning_moves, vec![Coords::new(1, 0)]);
+    /// ```
+    pub fn solve(&self, player: Color) -> Option<Solution> {
+        solve(self, player)
+    }
+
+    /// Return an immutable snapshot of this board that can be shared between threads (`Board` itself is not `Sync`).
+    pub fn freeze(&self) -> FrozenBoard {
+        FrozenBoard::new(&self.cells)
+    }
+
     /// Return the size of this board. Boards are always square.
     pub fn size(&self) -> CoordValue {
         self.cells.size
@@ -238,13 +331,119 @@ impl Board {
     pub fn find_attacked_bridges(&self, coords: Coords) -> Vec<Coords> {
         find_attacked_bridges(&self.cells, coords)
     }
+
+    /// Return all intact bridges of the given color, i.e. bridges whose two carrier cells are both empty.
+    ///
+    /// This includes bridges between two stones and bridges from a stone to one of the player's own edges
+    /// (e.g. from a black stone in the second row to the top edge). Stones that are already connected directly are not
+    /// reported as bridges between each other.
+    ///
+    /// ```
+    /// # use hexgame::{Board, Bridge, Color, Coords, Edge};
+    /// let mut board = Board::new(5);
+    /// board.play(Coords::new(1, 1), Color::Black).unwrap(); // b2
+    /// board.play(Coords::new(2, 2), Color::Black).unwrap(); // c3
+    ///
+    /// assert_eq!(board.bridges(Color::Black), vec![
+    ///     Bridge { start: Coords::new(1, 1), end: Edge::Top.into(), carriers: [Coords::new(0, 1), Coords::new(0, 2)] },
+    ///     Bridge { start: Coords::new(1, 1), end: Coords::new(2, 2).into(), carriers: [Coords::new(1, 2), Coords::new(2, 1)] },
+    /// ]);
+    /// ```
+    pub fn bridges(&self, color: Color) -> Vec<Bridge> {
+        bridges(self, color)
+    }
+
+    /// Return all bridges of the given color where the opponent has taken one carrier and the other one is still empty.
+    /// The player can save these bridges by playing the empty carrier (compare `find_attacked_bridges`).
+    pub fn broken_bridges(&self, color: Color) -> Vec<Bridge> {
+        broken_bridges(self, color)
+    }
+
+    /// Return all groups of connected stones of the given color, including their liberties and the edges they touch.
+    /// Groups are ordered by their first stone in row-major order.
+    ///
+    /// ```
+    /// # use hexgame::{Board, Color, Coords, Edge};
+    /// let mut board = Board::new(3);
+    /// board.play(Coords::new(0, 0), Color::Black).unwrap();
+    /// board.play(Coords::new(1, 0), Color::Black).unwrap();
+    ///
+    /// let groups = board.groups(Color::Black);
+    /// assert_eq!(groups.len(), 1);
+    /// assert_eq!(groups[0].stones, vec![Coords::new(0, 0), Coords::new(1, 0)]);
+    /// assert_eq!(groups[0].liberties, vec![Coords::new(0, 1), Coords::new(1, 1), Coords::new(2, 0)]);
+    /// assert_eq!(groups[0].edges, vec![Edge::Left, Edge::Top]);
+    /// ```
+    pub fn groups(&self, color: Color) -> Vec<Group> {
+        groups(&self.cells, color)
+    }
+
+    /// Return the group containing the stone at the given coordinates. Return None if the cell is empty.
+    pub fn group_of(&self, coords: Coords) -> Option<Group> {
+        group_of(&self.cells, coords)
+    }
+
+    /// Return all empty cells that connect the player's edges immediately, in row-major order.
+    /// Return an empty vector if the player has already connected their edges.
+    ///
+    /// ```
+    /// # use hexgame::{Board, Color, Coords};
+    /// let mut board = Board::new(3);
+    /// board.play(Coords::new(0, 1), Color::Black).unwrap();
+    /// board.play(Coords::new(2, 0), Color::Black).unwrap();
+    ///
+    /// // a2 and b2 both connect b1 and a3
+    /// assert_eq!(board.winning_moves(Color::Black), vec![Coords::new(1, 0), Coords::new(1, 1)]);
+    /// ```
+    ///
+    /// This method does not try every move. Instead, it looks for empty cells that are adjacent to both the set of
+    /// stones connected to the first edge and the set connected to the second edge.
+    pub fn winning_moves(&self, color: Color) -> Vec<Coords> {
+        winning_moves(&self.cells, color)
+    }
+
+    /// Return all empty cells that create a new winning move for the player, i.e. cells that win in two moves if the
+    /// opponent does not respond. Cells that win immediately (see `winning_moves`) are not included.
+    /// Return an empty vector if the player has already connected their edges.
+    ///
+    /// Like `winning_moves`, this uses the sets of stones adjacent to each empty cell instead of trying every pair of
+    /// moves: A threat is connected to one edge and touches (directly or via a common set of stones) a cell that is
+    /// connected to the other edge.
+    pub fn threats(&self, color: Color) -> Vec<Coords> {
+        threats(&self.cells, color)
+    }
+
+    /// Return all ongoing ladders of the given player in the second and third row along their own edges.
+    ///
+    /// A ladder is reported for each end of a line of blocked stones where the player can push with a threat to
+    /// connect. The ladder has an escape if the first stone ahead of it (between the ladder row and the edge) belongs
+    /// to the player. See `Ladder` for an example.
+    pub fn ladders(&self, color: Color) -> Vec<Ladder> {
+        ladders(self, color)
+    }
+}
+
+/// With the `serde` feature, boards are serialized as `StoneMatrix`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_stone_matrix().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stones = StoneMatrix::deserialize(deserializer)?;
+        Board::from_stone_matrix(stones).map_err(serde::de::Error::custom)
+    }
 }
 
 fn check_board_size(input: usize) -> Result<CoordValue, InvalidBoard> {
     input
         .try_into()
         .ok()
-        .filter(|&size| MIN_BOARD_SIZE <= size && size <= MAX_BOARD_SIZE)
+        .filter(|size| (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(size))
         .ok_or(InvalidBoard::SizeOutOfBounds(
             input,
             MIN_BOARD_SIZE,
@@ -430,6 +629,19 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut board = Board::new(2);
+        board.play(Coords::new(0, 1), Color::Black).unwrap();
+        let string = serde_json::to_string(&board).unwrap();
+        assert_eq!(string, r#"[[null,"black"],[null,null]]"#);
+
+        let loaded_board: Board = serde_json::from_str(&string).unwrap();
+        assert_eq!(loaded_board.to_stone_matrix(), board.to_stone_matrix());
+        assert!(serde_json::from_str::<Board>("[[null]]").is_err());
+    }
+
     #[test]
     fn test_get_neighbors() {
         let board = Board::new(3);
@@ -0,0 +1,515 @@
+use crate::board::{Board, MAX_BOARD_SIZE, MIN_BOARD_SIZE};
+use crate::color::Color;
+use crate::coords::{CoordValue, Coords};
+use crate::errors::ParseBookError;
+use crate::game::Game;
+use crate::moves::Move;
+use crate::player::Player;
+use crate::symmetry::Symmetry;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A move recommended by an `OpeningBook`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BookMove {
+    pub coords: Coords,
+    /// Relative weight of this move, e.g. how often it has been played. Larger is better.
+    pub weight: u32,
+}
+
+/// An opening book maps positions to recommended moves with weights.
+///
+/// Positions are stored in a canonical form (see `Symmetry`), so a move added for one position is also recommended
+/// (mirrored accordingly) in all symmetric positions, and the weights of symmetric moves are merged.
+/// If a position is symmetric itself (e.g. the empty board), equivalent moves are merged as well and only one of them
+/// is returned.
+///
+/// Books can be built from game records with `add_game` and stored in a simple text format via `Display` and `FromStr`.
+/// Each line contains a position and its moves, separated by whitespace: the board size, the player to move,
+/// Black's stones, White's stones (comma-separated, or "-" if there are none) and the moves with their weights.
+/// Lines starting with '#' are comments.
+/// ```text
+/// # size player black white moves
+/// 5 white c3 - b4:3,c2:1
+/// ```
+///
+/// ```
+/// # use hexgame::{Coords, Game, OpeningBook};
+/// let mut game = Game::new(5);
+/// game.play(Coords::new(2, 1)).unwrap();
+/// game.play(Coords::new(3, 2)).unwrap();
+/// let book = OpeningBook::from_games(&[game], 10);
+///
+/// // The book recommends b3 in the empty position and c4 in reply.
+/// let mut game = Game::new(5);
+/// assert_eq!(book.get_moves(&game)[0].coords, Coords::new(2, 1));
+/// game.play(Coords::new(2, 1)).unwrap();
+/// assert_eq!(book.get_moves(&game)[0].coords, Coords::new(3, 2));
+///
+/// let text = book.to_string();
+/// assert_eq!(text.parse::<OpeningBook>().unwrap(), book);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpeningBook {
+    // Key: the canonical board (as in `Board::to_bytes`) followed by the player to move.
+    // Moves are stored in the coordinates of the canonical board.
+    entries: HashMap<Vec<u8>, Vec<BookMove>>,
+}
+
+impl OpeningBook {
+    /// Create an empty book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a book from the first `max_moves` moves of the given games, see `add_game`.
+    pub fn from_games<'a, I: IntoIterator<Item = &'a Game>>(games: I, max_moves: usize) -> Self {
+        let mut book = Self::new();
+        for game in games {
+            book.add_game(game, max_moves);
+        }
+        book
+    }
+
+    /// Add each of the first `max_moves` moves of the given game with a weight of 1. Swaps are not added to the book.
+    pub fn add_game(&mut self, game: &Game, max_moves: usize) {
+        let mut position = game.initial_position();
+        for mv in game.get_moves().iter().take(max_moves) {
+            if let Move::Place(coords) = mv {
+                self.add_move(&position, *coords, 1);
+            }
+            position.play_move(*mv).expect("Moves of a game are valid");
+        }
+    }
+
+    /// Add the given move in the current position of the game. If the move (or a symmetric move in a symmetric position)
+    /// is already in the book, the weights are added up. Nothing happens if the game has ended.
+    pub fn add_move(&mut self, game: &Game, coords: Coords, weight: u32) {
+        let player = match game.get_current_player() {
+            Some(player) => player,
+            None => return,
+        };
+        let size = game.get_board().size();
+        let (key, symmetries) = canonical_key(game.get_board(), player);
+        // If the position is symmetric itself (e.g. the empty board), use the smallest of the equivalent moves.
+        let coords = symmetries
+            .iter()
+            .map(|symmetry| symmetry.apply_to_coords(coords, size))
+            .min_by_key(|coords| (coords.row, coords.column))
+            .unwrap();
+
+        let moves = self.entries.entry(key).or_default();
+        match moves.iter_mut().find(|mv| mv.coords == coords) {
+            Some(mv) => mv.weight = mv.weight.saturating_add(weight),
+            None => moves.push(BookMove { coords, weight }),
+        }
+    }
+
+    /// Return the moves recommended in the current position of the game, the move with the largest weight first.
+    /// Return an empty vector if the position is not in the book.
+    pub fn get_moves(&self, game: &Game) -> Vec<BookMove> {
+        let player = match game.get_current_player() {
+            Some(player) => player,
+            None => return vec![],
+        };
+        let size = game.get_board().size();
+        let (key, symmetries) = canonical_key(game.get_board(), player);
+        let symmetry = symmetries[0];
+
+        // All symmetries are involutions, so applying the symmetry again maps back to the game's board.
+        let mut moves: Vec<BookMove> = self
+            .entries
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .map(|mv| BookMove {
+                coords: symmetry.apply_to_coords(mv.coords, size),
+                weight: mv.weight,
+            })
+            .collect();
+        moves.sort_by_key(|mv| std::cmp::Reverse(mv.weight));
+        moves
+    }
+
+    /// Return the number of positions in this book.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return true if this book contains no positions.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Return the smallest key among all symmetric variants of the position, and all symmetries producing it.
+fn canonical_key(board: &Board, player: Color) -> (Vec<u8>, Vec<Symmetry>) {
+    let keys: Vec<(Vec<u8>, Symmetry)> = Symmetry::ALL
+        .iter()
+        .map(|symmetry| {
+            let mut key = symmetry.apply_to_board(board).to_bytes();
+            key.push(match symmetry.apply_to_color(player) {
+                Color::Black => 1,
+                Color::White => 2,
+            });
+            (key, *symmetry)
+        })
+        .collect();
+    let min_key = keys.iter().map(|(key, _)| key).min().unwrap().clone();
+    let symmetries = keys
+        .into_iter()
+        .filter(|(key, _)| *key == min_key)
+        .map(|(_, symmetry)| symmetry)
+        .collect();
+    (min_key, symmetries)
+}
+
+fn format_cells<I: Iterator<Item = String>>(cells: I) -> String {
+    let cells: Vec<String> = cells.collect();
+    if cells.is_empty() {
+        "-".to_string()
+    } else {
+        cells.join(",")
+    }
+}
+
+impl fmt::Display for OpeningBook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# size player black white moves")?;
+
+        let mut keys: Vec<&Vec<u8>> = self.entries.keys().collect();
+        keys.sort();
+        for key in keys {
+            let (player, board) = key.split_last().unwrap();
+            let board = Board::from_bytes(board).expect("Keys are valid boards");
+            let player = if *player == 1 { "black" } else { "white" };
+            let stones = |color: Color| {
+                format_cells(
+                    (0..board.size())
+                        .flat_map(|row| (0..board.size()).map(move |column| Coords { row, column }))
+                        .filter(|coords| board.get_color(*coords) == Some(color))
+                        .map(|coords| coords.to_string()),
+                )
+            };
+            let moves = format_cells(
+                self.entries[key]
+                    .iter()
+                    .map(|mv| format!("{}:{}", mv.coords, mv.weight)),
+            );
+            writeln!(
+                f,
+                "{} {} {} {} {}",
+                board.size(),
+                player,
+                stones(Color::Black),
+                stones(Color::White),
+                moves
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for OpeningBook {
+    type Err = ParseBookError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut book = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid =
+                |token: &str| ParseBookError::InvalidValue(line_number, token.to_string());
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 5 {
+                return Err(ParseBookError::WrongFieldCount(line_number, fields.len()));
+            }
+
+            let size = fields[0]
+                .parse::<CoordValue>()
+                .ok()
+                .filter(|size| (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(size))
+                .ok_or_else(|| invalid(fields[0]))?;
+            let player = match fields[1] {
+                "black" => Color::Black,
+                "white" => Color::White,
+                token => return Err(invalid(token)),
+            };
+            let parse_coords = |token: &str| {
+                token
+                    .parse::<Coords>()
+                    .ok()
+                    .filter(|coords| coords.is_on_board_with_size(size))
+                    .ok_or_else(|| invalid(token))
+            };
+
+            let mut stones = vec![vec![None; size as usize]; size as usize];
+            for (field, color) in [(fields[2], Color::Black), (fields[3], Color::White)] {
+                for token in split_cells(field) {
+                    let coords = parse_coords(token)?;
+                    let cell = &mut stones[coords.row as usize][coords.column as usize];
+                    if cell.is_some() {
+                        return Err(invalid(token));
+                    }
+                    *cell = Some(color);
+                }
+            }
+            let game = Game::load(stones, Some(player)).expect("Size and current player are valid");
+            if game.get_current_player().is_none() {
+                return Err(ParseBookError::FinishedPosition(line_number));
+            }
+
+            for token in split_cells(fields[4]) {
+                let (coords, weight) = token.split_once(':').ok_or_else(|| invalid(token))?;
+                let coords = parse_coords(coords)?;
+                let weight = weight.parse::<u32>().map_err(|_| invalid(token))?;
+                if game.get_board().get_color(coords).is_some() {
+                    return Err(invalid(token));
+                }
+                book.add_move(&game, coords, weight);
+            }
+        }
+        Ok(book)
+    }
+}
+
+fn split_cells(field: &str) -> impl Iterator<Item = &str> {
+    field.split(',').filter(|token| *token != "-")
+}
+
+/// A player that plays moves from an `OpeningBook` and asks another player (e.g. `Mcts`) once the book is exhausted.
+///
+/// In book positions, the move with the largest weight is chosen.
+///
+/// ```
+/// # use hexgame::{BookPlayer, Coords, Game, Mcts, MctsOptions, Move, OpeningBook, Player};
+/// let mut book = OpeningBook::new();
+/// book.add_move(&Game::new(5), Coords::new(1, 3), 1);
+/// let mut player = BookPlayer::new(book, Mcts::new(MctsOptions::default()));
+///
+/// assert_eq!(player.choose_move(&Game::new(5)), Some(Move::Place(Coords::new(1, 3))));
+/// ```
+pub struct BookPlayer<P: Player> {
+    book: OpeningBook,
+    player: P,
+}
+
+impl<P: Player> BookPlayer<P> {
+    pub fn new(book: OpeningBook, player: P) -> Self {
+        Self { book, player }
+    }
+
+    /// Return the opening book.
+    pub fn get_book(&self) -> &OpeningBook {
+        &self.book
+    }
+
+    /// Return the player used outside of the book.
+    pub fn get_player(&self) -> &P {
+        &self.player
+    }
+
+    /// Return the player used outside of the book mutably.
+    pub fn get_player_mut(&mut self) -> &mut P {
+        &mut self.player
+    }
+}
+
+impl<P: Player> Player for BookPlayer<P> {
+    fn choose_move(&mut self, game: &Game) -> Option<Move> {
+        match self.book.get_moves(game).first() {
+            Some(mv) => Some(Move::Place(mv.coords)),
+            None => self.player.choose_move(game),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symmetric_moves_are_merged() {
+        let mut book = OpeningBook::new();
+        let empty = Game::new(5);
+        book.add_move(&empty, Coords::new(0, 1), 1);
+        // Rotated by 180°
+        book.add_move(&empty, Coords::new(4, 3), 2);
+        book.add_move(&empty, Coords::new(2, 2), 1);
+
+        assert_eq!(book.len(), 1);
+        // The empty board is symmetric under rotation, so b1 and d5 are equivalent moves.
+        assert_eq!(
+            book.get_moves(&empty),
+            vec![
+                BookMove {
+                    coords: Coords::new(0, 1),
+                    weight: 3
+                },
+                BookMove {
+                    coords: Coords::new(2, 2),
+                    weight: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_symmetric_positions() {
+        let mut book = OpeningBook::new();
+        let mut game = Game::new(4);
+        game.play(Coords::new(0, 1)).unwrap();
+        book.add_move(&game, Coords::new(1, 1), 1);
+
+        // The same position rotated by 180°
+        let mut rotated = Game::new(4);
+        rotated.play(Coords::new(3, 2)).unwrap();
+        assert_eq!(book.get_moves(&rotated)[0].coords, Coords::new(2, 2));
+
+        // The same position transposed with swapped colors, i.e. Black to move with a white stone on a2
+        let transposed = Game::load(
+            vec![
+                vec![None, None, None, None],
+                vec![Some(Color::White), None, None, None],
+                vec![None, None, None, None],
+                vec![None, None, None, None],
+            ],
+            Some(Color::Black),
+        )
+        .unwrap();
+        assert_eq!(book.get_moves(&transposed)[0].coords, Coords::new(1, 1));
+
+        // Not the same position: Black to move with a black stone on b1
+        let other = Game::load(
+            vec![
+                vec![None, Some(Color::Black), None, None],
+                vec![None, None, None, None],
+                vec![None, None, None, None],
+                vec![None, None, None, None],
+            ],
+            Some(Color::Black),
+        )
+        .unwrap();
+        assert!(book.get_moves(&other).is_empty());
+    }
+
+    #[test]
+    fn test_add_game_with_swap() {
+        let mut game = Game::new(5);
+        game.get_metadata_mut().swap_rule = true;
+        game.play(Coords::new(0, 2)).unwrap();
+        game.swap().unwrap();
+        game.play(Coords::new(3, 3)).unwrap();
+        game.play(Coords::new(4, 4)).unwrap();
+
+        let book = OpeningBook::from_games(&[game], 3);
+
+        // The swap itself is not in the book, but the reply after it. The fourth move is not in the book.
+        assert_eq!(book.len(), 2);
+        let mut position = Game::new(5);
+        position.get_metadata_mut().swap_rule = true;
+        position.play(Coords::new(0, 2)).unwrap();
+        position.swap().unwrap();
+        assert_eq!(
+            book.get_moves(&position),
+            vec![BookMove {
+                coords: Coords::new(3, 3),
+                weight: 1
+            }]
+        );
+        // Swapping produces an equivalent position (transposed with swapped colors), so the book also knows
+        // the transposed reply for White without a swap.
+        let mut position = Game::new(5);
+        position.play(Coords::new(0, 2)).unwrap();
+        assert_eq!(book.get_moves(&position)[0].coords, Coords::new(3, 3));
+    }
+
+    #[test]
+    fn test_text_format() {
+        let text = "
+            # A comment
+            3 black - - b2:5,a1:1
+            3 white b2 - a3:2
+        ";
+        let book: OpeningBook = text.parse().unwrap();
+
+        assert_eq!(book.len(), 2);
+        let mut game = Game::new(3);
+        assert_eq!(book.get_moves(&game)[0].weight, 5);
+        game.play(Coords::new(1, 1)).unwrap();
+        // With a stone on b2, the board is symmetric under rotation, so a3 is stored as the equivalent c1.
+        assert_eq!(book.get_moves(&game)[0].coords, Coords::new(0, 2));
+
+        let written = book.to_string();
+        assert!(written.starts_with("# size player black white moves\n"));
+        assert_eq!(written.parse::<OpeningBook>().unwrap(), book);
+    }
+
+    #[test]
+    fn test_invalid_text() {
+        let parse = |text: &str| text.parse::<OpeningBook>().err().unwrap();
+
+        assert_eq!(parse("3 black - -"), ParseBookError::WrongFieldCount(1, 4));
+        assert_eq!(
+            parse("\n1 black - - a1:1"),
+            ParseBookError::InvalidValue(2, "1".to_string())
+        );
+        assert_eq!(
+            parse("3 red - - a1:1"),
+            ParseBookError::InvalidValue(1, "red".to_string())
+        );
+        assert_eq!(
+            parse("3 black a1 a1 b1:1"),
+            ParseBookError::InvalidValue(1, "a1".to_string())
+        );
+        assert_eq!(
+            parse("3 black a1 - a1:1"),
+            ParseBookError::InvalidValue(1, "a1:1".to_string())
+        );
+        assert_eq!(
+            parse("3 black - - d1:1"),
+            ParseBookError::InvalidValue(1, "d1".to_string())
+        );
+        assert_eq!(
+            parse("3 black - - a1:x"),
+            ParseBookError::InvalidValue(1, "a1:x".to_string())
+        );
+        assert_eq!(
+            parse("2 black a1,a2 - b1:1"),
+            ParseBookError::FinishedPosition(1)
+        );
+    }
+
+    #[test]
+    fn test_book_player() {
+        struct FirstEmptyCell;
+        impl Player for FirstEmptyCell {
+            fn choose_move(&mut self, game: &Game) -> Option<Move> {
+                game.get_board()
+                    .get_empty_cells()
+                    .first()
+                    .map(|c| Move::Place(*c))
+            }
+        }
+
+        let mut book = OpeningBook::new();
+        book.add_move(&Game::new(3), Coords::new(1, 1), 1);
+        let mut player = BookPlayer::new(book, FirstEmptyCell);
+
+        let mut game = Game::new(3);
+        assert_eq!(
+            player.choose_move(&game),
+            Some(Move::Place(Coords::new(1, 1)))
+        );
+        game.play(Coords::new(1, 1)).unwrap();
+        assert_eq!(
+            player.choose_move(&game),
+            Some(Move::Place(Coords::new(0, 0)))
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use crate::board::Board;
+use crate::color::Color;
+use crate::coords::Coords;
+use crate::edges::{get_edges_of_color, CoordsOrEdge, Edge};
+
+/// A bridge connects a stone to another stone or to an edge of the same color via two carrier cells.
+/// If the opponent plays one carrier, the bridge can be saved by playing the other one.
+///
+/// ```text
+///  a  b  c
+/// 1\.  .  .\1
+///  2\.  .  ●\2
+///   3\●  .  .\3
+///      a  b  c
+/// ```
+/// On this board, Black has a bridge between a3 and c2 (carriers b2 and b3) and a bridge between c2 and the bottom edge
+/// (carriers c3 and b3).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bridge {
+    /// The stone where the bridge starts. For bridges between two stones, this is the first stone in row-major order.
+    pub start: Coords,
+    /// The other stone, or the edge.
+    pub end: CoordsOrEdge,
+    /// The two cells adjacent to both ends.
+    pub carriers: [Coords; 2],
+}
+
+// Offsets (row, column) of the six neighbors in the order of `get_neighbors`: left, top-left, top-right, right,
+// bottom-right, bottom-left. Consecutive neighbors (cyclically) are the carriers of a bridge in the direction of
+// `BRIDGE_OFFSETS` with the same index.
+const NEIGHBOR_OFFSETS: [(i16, i16); 6] = [(0, -1), (-1, 0), (-1, 1), (0, 1), (1, 0), (1, -1)];
+const BRIDGE_OFFSETS: [(i16, i16); 6] = [(-1, -1), (-2, 1), (-1, 2), (1, 1), (2, -1), (1, -2)];
+
+/// Return the cell at the given offset, or the edge if the offset leaves the board, or None if it leaves the board
+/// on more than one side.
+fn offset(board: &Board, coords: Coords, (row, column): (i16, i16)) -> Option<CoordsOrEdge> {
+    let size = board.size() as i16;
+    let row = coords.row as i16 + row;
+    let column = coords.column as i16 + column;
+    let row_inside = (0..size).contains(&row);
+    let column_inside = (0..size).contains(&column);
+    match (row_inside, column_inside) {
+        (true, true) => Some(Coords::new(row as u8, column as u8).into()),
+        (false, true) if row < 0 => Some(Edge::Top.into()),
+        (false, true) => Some(Edge::Bottom.into()),
+        (true, false) if column < 0 => Some(Edge::Left.into()),
+        (true, false) => Some(Edge::Right.into()),
+        (false, false) => None,
+    }
+}
+
+/// Return all bridges of the given color whose carriers are in one of the given states.
+fn find_bridges<F: Fn(Option<Color>, Option<Color>) -> bool>(
+    board: &Board,
+    color: Color,
+    carriers_match: F,
+) -> Vec<Bridge> {
+    let own_edges = get_edges_of_color(color);
+    let mut result = vec![];
+
+    for row in 0..board.size() {
+        for column in 0..board.size() {
+            let start = Coords::new(row, column);
+            if board.get_color(start) != Some(color) {
+                continue;
+            }
+
+            for i in 0..6 {
+                let first = offset(board, start, NEIGHBOR_OFFSETS[i]);
+                let second = offset(board, start, NEIGHBOR_OFFSETS[(i + 1) % 6]);
+                let (first, second) = match (first, second) {
+                    (Some(CoordsOrEdge::Coords(first)), Some(CoordsOrEdge::Coords(second))) => {
+                        (first, second)
+                    }
+                    _ => continue,
+                };
+                let end = match offset(board, start, BRIDGE_OFFSETS[i]) {
+                    Some(end) => end,
+                    None => continue,
+                };
+                let is_bridge_end = match end {
+                    // Report bridges between two stones only once.
+                    CoordsOrEdge::Coords(end) => {
+                        board.get_color(end) == Some(color)
+                            && (end.row, end.column) > (start.row, start.column)
+                    }
+                    CoordsOrEdge::Edge(edge) => own_edges.contains(&edge),
+                };
+                if is_bridge_end && carriers_match(board.get_color(first), board.get_color(second))
+                {
+                    result.push(Bridge {
+                        start,
+                        end,
+                        carriers: [first, second],
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+pub fn bridges(board: &Board, color: Color) -> Vec<Bridge> {
+    find_bridges(board, color, |first, second| {
+        first.is_none() && second.is_none()
+    })
+}
+
+pub fn broken_bridges(board: &Board, color: Color) -> Vec<Bridge> {
+    let opponent = Some(color.opponent_color());
+    find_bridges(board, color, |first, second| {
+        (first == opponent && second.is_none()) || (first.is_none() && second == opponent)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bridge<T: Into<CoordsOrEdge>>(start: &str, end: T, carriers: [&str; 2]) -> Bridge {
+        Bridge {
+            start: start.parse().unwrap(),
+            end: end.into(),
+            carriers: [carriers[0].parse().unwrap(), carriers[1].parse().unwrap()],
+        }
+    }
+
+    fn coords(s: &str) -> Coords {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_all_directions() {
+        let mut board = Board::new(5);
+        board.play(coords("c3"), Color::Black).unwrap();
+        for end in ["b2", "d1", "e2", "d4", "b5", "a4"] {
+            board.play(coords(end), Color::Black).unwrap();
+        }
+
+        let bridges = bridges(&board, Color::Black);
+
+        let center_bridges: Vec<&Bridge> = bridges
+            .iter()
+            .filter(|b| b.start == coords("c3") || b.end == coords("c3").into())
+            .collect();
+        assert_eq!(center_bridges.len(), 6);
+        assert!(bridges.contains(&bridge("b2", coords("c3"), ["c2", "b3"])));
+        assert!(bridges.contains(&bridge("c3", coords("b5"), ["c4", "b4"])));
+    }
+
+    #[test]
+    fn test_edge_bridges() {
+        // Black's a2 has a bridge to the top edge. White's b2 has one to the right edge, but the one to the left edge
+        // is blocked by a2. Bridges to the opponent's edges do not count.
+        let board: Board = "
+            .  .  .
+            ●  ○  .
+            .  .  .
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            bridges(&board, Color::Black),
+            vec![bridge("a2", Edge::Top, ["a1", "b1"])]
+        );
+        assert_eq!(
+            bridges(&board, Color::White),
+            vec![bridge("b2", Edge::Right, ["c1", "c2"])]
+        );
+    }
+
+    #[test]
+    fn test_broken_bridges() {
+        // Black's bridges b1 - c2 and b1 - a3 are attacked on b2, the bridge c2 - bottom edge is cut completely.
+        let board: Board = "
+            .  ●  .
+            .  ○  ●
+            ●  ○  ○
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            broken_bridges(&board, Color::Black),
+            vec![
+                bridge("b1", coords("c2"), ["c1", "b2"]),
+                bridge("b1", coords("a3"), ["b2", "a2"]),
+            ]
+        );
+        assert!(bridges(&board, Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_solid_connections_are_no_bridges() {
+        let board: Board = "
+            ●  .  .
+            ●  ●  .
+            .  .  .
+        "
+        .parse()
+        .unwrap();
+
+        // a1 and b2 are connected via a2 directly.
+        assert!(!bridges(&board, Color::Black)
+            .iter()
+            .any(|b| b.start == coords("a1") && b.end == coords("b2").into()));
+        assert!(broken_bridges(&board, Color::Black).is_empty());
+    }
+}
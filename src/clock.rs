@@ -0,0 +1,675 @@
+use crate::color::Color;
+use crate::coords::Coords;
+use crate::errors::InvalidMove;
+use crate::game::{Game, GameResult, Status};
+use crate::moves::Move;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The time control of a game, i.e. how much thinking time the players get.
+///
+/// Time controls can be parsed from strings (all values in seconds):
+///
+/// * `300`: absolute time of 5 minutes per player,
+/// * `300+5`: Fischer time control with 5 minutes per player and an increment of 5 seconds per move,
+/// * `300/30x5`: Byo-yomi with a main time of 5 minutes, followed by 5 periods of 30 seconds.
+/// * `unlimited`: no time control at all.
+///
+/// ```
+/// # use hexgame::TimeControl;
+/// # use std::time::Duration;
+/// let control: TimeControl = "300+5".parse().unwrap();
+/// assert_eq!(control, TimeControl::Fischer {
+///     initial: Duration::from_secs(300),
+///     increment: Duration::from_secs(5),
+/// });
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimeControl {
+    /// Players have unlimited time.
+    Unlimited,
+    /// Each player has a fixed amount of time for the whole game.
+    Absolute(Duration),
+    /// Each player starts with `initial` time and gains `increment` after each of their moves.
+    Fischer {
+        initial: Duration,
+        increment: Duration,
+    },
+    /// Each player has `main_time` for the whole game. Once it is used up, a player has `periods` periods of length `period`.
+    /// A period is only used up if a move takes longer than `period`. A player loses on time if all periods are used up.
+    ByoYomi {
+        main_time: Duration,
+        period: Duration,
+        periods: u32,
+    },
+}
+
+impl TimeControl {
+    fn initial_clock(&self) -> PlayerClock {
+        match *self {
+            TimeControl::Unlimited => PlayerClock::new(Duration::ZERO, 0),
+            TimeControl::Absolute(time) => PlayerClock::new(time, 0),
+            TimeControl::Fischer { initial, .. } => PlayerClock::new(initial, 0),
+            TimeControl::ByoYomi {
+                main_time, periods, ..
+            } => PlayerClock::new(main_time, periods),
+        }
+    }
+}
+
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimeControl::Unlimited => write!(f, "unlimited"),
+            TimeControl::Absolute(time) => write!(f, "{}", time.as_secs()),
+            TimeControl::Fischer { initial, increment } => {
+                write!(f, "{}+{}", initial.as_secs(), increment.as_secs())
+            }
+            TimeControl::ByoYomi {
+                main_time,
+                period,
+                periods,
+            } => write!(
+                f,
+                "{}/{}x{}",
+                main_time.as_secs(),
+                period.as_secs(),
+                periods
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for TimeControl {
+    type Err = ParseTimeControlError;
+
+    /// Parse a time control from one of the formats described in `TimeControl`.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let error = || ParseTimeControlError {
+            description: format!("Invalid time control: {}", string),
+        };
+        let seconds = |s: &str| {
+            s.parse::<u64>()
+                .map(Duration::from_secs)
+                .map_err(|_| error())
+        };
+
+        if string == "unlimited" {
+            Ok(TimeControl::Unlimited)
+        } else if let Some((initial, increment)) = string.split_once('+') {
+            Ok(TimeControl::Fischer {
+                initial: seconds(initial)?,
+                increment: seconds(increment)?,
+            })
+        } else if let Some((main_time, byo_yomi)) = string.split_once('/') {
+            let (period, periods) = byo_yomi.split_once('x').ok_or_else(error)?;
+            Ok(TimeControl::ByoYomi {
+                main_time: seconds(main_time)?,
+                period: seconds(period)?,
+                periods: periods.parse().map_err(|_| error())?,
+            })
+        } else {
+            seconds(string).map(TimeControl::Absolute)
+        }
+    }
+}
+
+/// Returned by `TimeControl::from_str` if the string cannot be parsed.
+#[derive(Debug)]
+pub struct ParseTimeControlError {
+    description: String,
+}
+
+impl fmt::Display for ParseTimeControlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.description)
+    }
+}
+
+impl Error for ParseTimeControlError {}
+
+/// A source of monotonic time. Clocks only use differences between two calls of `now`,
+/// so the starting point is arbitrary.
+///
+/// Use `SystemTimeSource` for real games and `ManualTimeSource` for deterministic tests.
+pub trait TimeSource {
+    /// Return the time that has passed since an arbitrary, but fixed starting point.
+    fn now(&self) -> Duration;
+}
+
+impl<T: TimeSource + ?Sized> TimeSource for &T {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+/// A `TimeSource` that uses the system's monotonic clock.
+pub struct SystemTimeSource {
+    start: Instant,
+}
+
+impl SystemTimeSource {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemTimeSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for SystemTimeSource {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A `TimeSource` that only advances when `advance` is called. This is useful for tests.
+///
+/// ```
+/// # use hexgame::{ManualTimeSource, TimeSource};
+/// # use std::time::Duration;
+/// let source = ManualTimeSource::new();
+/// source.advance(Duration::from_secs(3));
+/// assert_eq!(source.now(), Duration::from_secs(3));
+/// ```
+#[derive(Debug, Default)]
+pub struct ManualTimeSource {
+    now: Cell<Duration>,
+}
+
+impl ManualTimeSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let the given amount of time pass.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl TimeSource for ManualTimeSource {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct PlayerClock {
+    remaining: Duration,
+    periods_left: u32,
+}
+
+impl PlayerClock {
+    fn new(remaining: Duration, periods_left: u32) -> Self {
+        Self {
+            remaining,
+            periods_left,
+        }
+    }
+}
+
+/// Clocks of both players.
+///
+/// At most one clock is running at any time. Use `start` to start the clock of a player and `stop` to stop it after the player has moved.
+/// `stop` will deduct the used time and apply increments or byo-yomi periods according to the time control.
+pub struct Clock<S: TimeSource> {
+    control: TimeControl,
+    source: S,
+    clocks: [PlayerClock; 2],
+    // Player whose clock is running and the time when it was started.
+    running: Option<(Color, Duration)>,
+}
+
+impl<S: TimeSource> Clock<S> {
+    /// Create new clocks for both players. No clock is running initially.
+    pub fn new(control: TimeControl, source: S) -> Self {
+        Self {
+            control,
+            source,
+            clocks: [control.initial_clock(); 2],
+            running: None,
+        }
+    }
+
+    /// Return the time control used by this clock.
+    pub fn get_time_control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Start the clock of the given player. If another clock is running, it will be stopped first.
+    pub fn start(&mut self, color: Color) {
+        if let Some((running_color, _)) = self.running {
+            // Only the stopped player may time out here. They will be detected by `has_timed_out`.
+            let _ = self.stop(running_color);
+        }
+        self.running = Some((color, self.source.now()));
+    }
+
+    /// Stop the clock of the given player and deduct the time they have used.
+    ///
+    /// Returns an error if the player has exceeded their time. If the clock of the player is not running, this method does nothing.
+    pub fn stop(&mut self, color: Color) -> Result<(), Timeout> {
+        match self.running {
+            Some((running_color, start)) if running_color == color => {
+                self.running = None;
+                let elapsed = self.source.now().saturating_sub(start);
+                let control = self.control;
+                let clock = &mut self.clocks[color_index(color)];
+                if deduct(control, clock, elapsed) {
+                    if let TimeControl::Fischer { increment, .. } = control {
+                        clock.remaining += increment;
+                    }
+                    Ok(())
+                } else {
+                    Err(Timeout(color))
+                }
+            }
+            _ => {
+                if self.has_timed_out(color) {
+                    Err(Timeout(color))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Return the remaining main time of the given player, taking a running clock into account.
+    ///
+    /// For `TimeControl::Unlimited` this is always zero.
+    pub fn get_remaining(&self, color: Color) -> Duration {
+        let mut clock = self.clocks[color_index(color)];
+        deduct(self.control, &mut clock, self.get_running_time(color));
+        clock.remaining
+    }
+
+    /// Return the number of byo-yomi periods the given player has left, taking a running clock into account.
+    pub fn get_periods_left(&self, color: Color) -> u32 {
+        let mut clock = self.clocks[color_index(color)];
+        deduct(self.control, &mut clock, self.get_running_time(color));
+        clock.periods_left
+    }
+
+    /// Return whether the given player has exceeded their time, taking a running clock into account.
+    pub fn has_timed_out(&self, color: Color) -> bool {
+        let mut clock = self.clocks[color_index(color)];
+        !deduct(self.control, &mut clock, self.get_running_time(color))
+    }
+
+    fn get_running_time(&self, color: Color) -> Duration {
+        match self.running {
+            Some((running_color, start)) if running_color == color => {
+                self.source.now().saturating_sub(start)
+            }
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Deduct `elapsed` from the clock for a single move. Increments are not applied here.
+/// Return false if the player has exceeded their time.
+fn deduct(control: TimeControl, clock: &mut PlayerClock, elapsed: Duration) -> bool {
+    match control {
+        TimeControl::Unlimited => true,
+        TimeControl::Absolute(_) | TimeControl::Fischer { .. } => {
+            match clock.remaining.checked_sub(elapsed) {
+                Some(remaining) => {
+                    clock.remaining = remaining;
+                    true
+                }
+                None => {
+                    clock.remaining = Duration::ZERO;
+                    false
+                }
+            }
+        }
+        TimeControl::ByoYomi { period, .. } => {
+            if let Some(remaining) = clock.remaining.checked_sub(elapsed) {
+                clock.remaining = remaining;
+                return true;
+            }
+            let mut overtime = elapsed - clock.remaining;
+            clock.remaining = Duration::ZERO;
+            // Each period that is fully used up is lost.
+            while overtime > period && clock.periods_left > 0 {
+                overtime -= period;
+                clock.periods_left -= 1;
+            }
+            clock.periods_left > 0
+        }
+    }
+}
+
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::Black => 0,
+        Color::White => 1,
+    }
+}
+
+/// Returned by `Clock::stop` if the given player has exceeded their time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Timeout(pub Color);
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{} has exceeded their time", self.0)
+    }
+}
+
+impl Error for Timeout {}
+
+/// A game together with clocks for both players.
+///
+/// The clock of the current player is running all the time. `play` deducts the used time and lets the player lose if they have exceeded their time.
+///
+/// ```
+/// # use hexgame::{Color, Coords, Game, ManualTimeSource, TimeControl, TimedGame, WinReason};
+/// # use std::time::Duration;
+/// let source = ManualTimeSource::new();
+/// let control = TimeControl::Absolute(Duration::from_secs(60));
+/// let mut timed_game = TimedGame::new(Game::new(9), control, &source);
+///
+/// source.advance(Duration::from_secs(61));
+/// assert!(timed_game.play(Coords::new(4, 4)).is_err());
+///
+/// let result = timed_game.get_game().get_result().unwrap();
+/// assert_eq!(result.winner, Color::White);
+/// assert_eq!(result.reason, WinReason::Timeout);
+/// ```
+pub struct TimedGame<S: TimeSource> {
+    game: Game,
+    clock: Clock<S>,
+}
+
+impl<S: TimeSource> TimedGame<S> {
+    /// Create a timed game and start the clock of the current player.
+    pub fn new(game: Game, control: TimeControl, source: S) -> Self {
+        let mut clock = Clock::new(control, source);
+        if let Some(current_player) = game.get_current_player() {
+            clock.start(current_player);
+        }
+        Self { game, clock }
+    }
+
+    /// Return the game.
+    pub fn get_game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Return the clocks.
+    pub fn get_clock(&self) -> &Clock<S> {
+        &self.clock
+    }
+
+    /// Let the current player place a stone at the given coordinates (see `Game::play`).
+    ///
+    /// If the current player has exceeded their time, the stone is not placed. Instead, the player loses the game
+    /// and this method returns `InvalidMove::GameOver`.
+    /// After invalid moves, the clock of the current player keeps running.
+    pub fn play(&mut self, coords: Coords) -> Result<(), InvalidMove> {
+        self.play_move(Move::Place(coords))
+    }
+
+    /// Let the current player play the given move, i.e. place a stone or swap (see `Game::play_move`).
+    /// Clocks are handled in the same way as in `play`.
+    pub fn play_move(&mut self, mv: Move) -> Result<(), InvalidMove> {
+        let current_player = match self.game.get_status() {
+            Status::Ongoing(color) => color,
+            Status::Finished(_) => return Err(InvalidMove::GameOver),
+        };
+
+        if self.check_time().is_some() {
+            return Err(InvalidMove::GameOver);
+        }
+
+        self.game.play_move(mv)?;
+
+        let timeout = self.clock.stop(current_player).is_err();
+        match self.game.get_current_player() {
+            // The time may have run out just while the move was being played.
+            Some(_) if timeout => self.game.lose_on_time(current_player)?,
+            Some(next_player) => self.clock.start(next_player),
+            None => (),
+        }
+
+        Ok(())
+    }
+
+    /// Let the given player resign (see `Game::resign`). The running clock is stopped, no matter who resigns.
+    pub fn resign(&mut self, color: Color) -> Result<(), InvalidMove> {
+        let current_player = self.game.get_current_player();
+        self.game.resign(color)?;
+        if let Some(current_player) = current_player {
+            let _ = self.clock.stop(current_player);
+        }
+        Ok(())
+    }
+
+    /// Check whether the current player has exceeded their time. If so, they lose the game and the result is returned.
+    ///
+    /// Call this method regularly if you need to detect timeouts while waiting for a move.
+    pub fn check_time(&mut self) -> Option<GameResult> {
+        let current_player = self.game.get_current_player()?;
+        if self.clock.has_timed_out(current_player) {
+            let _ = self.clock.stop(current_player);
+            self.game.lose_on_time(current_player).ok()?;
+            self.game.get_result()
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::WinReason;
+
+    fn seconds(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_parse_time_control() {
+        assert_eq!(
+            "unlimited".parse::<TimeControl>().unwrap(),
+            TimeControl::Unlimited
+        );
+        assert_eq!(
+            "300".parse::<TimeControl>().unwrap(),
+            TimeControl::Absolute(seconds(300))
+        );
+        assert_eq!(
+            "300+5".parse::<TimeControl>().unwrap(),
+            TimeControl::Fischer {
+                initial: seconds(300),
+                increment: seconds(5)
+            }
+        );
+        assert_eq!(
+            "300/30x5".parse::<TimeControl>().unwrap(),
+            TimeControl::ByoYomi {
+                main_time: seconds(300),
+                period: seconds(30),
+                periods: 5
+            }
+        );
+        assert!("300+".parse::<TimeControl>().is_err());
+        assert!("300/30".parse::<TimeControl>().is_err());
+        assert!("five".parse::<TimeControl>().is_err());
+    }
+
+    #[test]
+    fn test_time_control_to_string_cycle() {
+        for string in ["unlimited", "300", "300+5", "300/30x5"] {
+            let control: TimeControl = string.parse().unwrap();
+            assert_eq!(control.to_string(), string);
+        }
+    }
+
+    #[test]
+    fn test_absolute_time() {
+        let source = ManualTimeSource::new();
+        let mut clock = Clock::new(TimeControl::Absolute(seconds(10)), &source);
+
+        clock.start(Color::Black);
+        source.advance(seconds(4));
+        assert_eq!(clock.get_remaining(Color::Black), seconds(6));
+        assert_eq!(clock.stop(Color::Black), Ok(()));
+
+        clock.start(Color::Black);
+        source.advance(seconds(7));
+        assert!(clock.has_timed_out(Color::Black));
+        assert_eq!(clock.stop(Color::Black), Err(Timeout(Color::Black)));
+        assert_eq!(clock.get_remaining(Color::White), seconds(10));
+    }
+
+    #[test]
+    fn test_fischer_increment() {
+        let source = ManualTimeSource::new();
+        let control = TimeControl::Fischer {
+            initial: seconds(10),
+            increment: seconds(5),
+        };
+        let mut clock = Clock::new(control, &source);
+
+        clock.start(Color::White);
+        source.advance(seconds(8));
+        assert_eq!(clock.stop(Color::White), Ok(()));
+        assert_eq!(clock.get_remaining(Color::White), seconds(7));
+    }
+
+    #[test]
+    fn test_byo_yomi() {
+        let source = ManualTimeSource::new();
+        let control = TimeControl::ByoYomi {
+            main_time: seconds(10),
+            period: seconds(5),
+            periods: 2,
+        };
+        let mut clock = Clock::new(control, &source);
+
+        // Uses up the main time, but stays within the first period
+        clock.start(Color::Black);
+        source.advance(seconds(14));
+        assert_eq!(clock.stop(Color::Black), Ok(()));
+        assert_eq!(clock.get_remaining(Color::Black), Duration::ZERO);
+        assert_eq!(clock.get_periods_left(Color::Black), 2);
+
+        // Uses up one period
+        clock.start(Color::Black);
+        source.advance(seconds(7));
+        assert_eq!(clock.stop(Color::Black), Ok(()));
+        assert_eq!(clock.get_periods_left(Color::Black), 1);
+
+        // Uses up the last period
+        clock.start(Color::Black);
+        source.advance(seconds(6));
+        assert_eq!(clock.stop(Color::Black), Err(Timeout(Color::Black)));
+    }
+
+    #[test]
+    fn test_unlimited_time() {
+        let source = ManualTimeSource::new();
+        let mut clock = Clock::new(TimeControl::Unlimited, &source);
+        clock.start(Color::Black);
+        source.advance(seconds(1_000_000));
+        assert_eq!(clock.stop(Color::Black), Ok(()));
+    }
+
+    #[test]
+    fn test_timed_game_switches_clocks() {
+        let source = ManualTimeSource::new();
+        let control = TimeControl::Absolute(seconds(10));
+        let mut timed_game = TimedGame::new(Game::new(3), control, &source);
+
+        source.advance(seconds(3));
+        timed_game.play(Coords::new(0, 0)).unwrap();
+        source.advance(seconds(4));
+
+        assert_eq!(
+            timed_game.get_clock().get_remaining(Color::Black),
+            seconds(7)
+        );
+        assert_eq!(
+            timed_game.get_clock().get_remaining(Color::White),
+            seconds(6)
+        );
+    }
+
+    #[test]
+    fn test_timed_game_loses_on_time() {
+        let source = ManualTimeSource::new();
+        let control = TimeControl::Absolute(seconds(10));
+        let mut timed_game = TimedGame::new(Game::new(3), control, &source);
+
+        timed_game.play(Coords::new(0, 0)).unwrap();
+        source.advance(seconds(11));
+
+        assert_eq!(
+            timed_game.check_time(),
+            Some(GameResult::new(Color::Black, WinReason::Timeout))
+        );
+        assert_eq!(
+            timed_game.play(Coords::new(1, 1)),
+            Err(InvalidMove::GameOver)
+        );
+    }
+
+    #[test]
+    fn test_timed_game_resign_stops_running_clock() {
+        let source = ManualTimeSource::new();
+        let control = TimeControl::Absolute(seconds(10));
+        let mut timed_game = TimedGame::new(Game::new(3), control, &source);
+
+        source.advance(seconds(2));
+        // White resigns while Black is on move.
+        timed_game.resign(Color::White).unwrap();
+        source.advance(seconds(20));
+
+        assert_eq!(
+            timed_game.get_clock().get_remaining(Color::Black),
+            seconds(8)
+        );
+        assert!(!timed_game.get_clock().has_timed_out(Color::Black));
+    }
+
+    #[test]
+    fn test_timed_game_swap() {
+        let source = ManualTimeSource::new();
+        let control = TimeControl::Absolute(seconds(10));
+        let mut game = Game::new(3);
+        game.get_metadata_mut().swap_rule = true;
+        let mut timed_game = TimedGame::new(game, control, &source);
+
+        source.advance(seconds(1));
+        timed_game
+            .play_move(Move::Place(Coords::new(0, 0)))
+            .unwrap();
+        source.advance(seconds(3));
+        timed_game.play_move(Move::Swap).unwrap();
+        source.advance(seconds(2));
+
+        assert_eq!(
+            timed_game.get_game().get_current_player(),
+            Some(Color::Black)
+        );
+        assert_eq!(
+            timed_game.get_clock().get_remaining(Color::Black),
+            seconds(7)
+        );
+        assert_eq!(
+            timed_game.get_clock().get_remaining(Color::White),
+            seconds(7)
+        );
+    }
+}
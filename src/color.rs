@@ -1,8 +1,12 @@
+use std::fmt;
+
 /// This enum represents the two players. The first player is always `Black`.
 ///
 /// When displaying boards we use the symbol ● for Black and ○ for White.
 /// Note that when using a dark theme, apparent colors might be reversed.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Color {
     /// Needs to connect top to bottom. Always starts the game.
     Black,
@@ -19,3 +23,12 @@ impl Color {
         }
     }
 }
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Black => write!(f, "Black"),
+            Color::White => write!(f, "White"),
+        }
+    }
+}
@@ -34,7 +34,7 @@ pub type CoordValue = u8;
 /// let other_coords = Coords::from_str("a8").unwrap();
 /// assert_eq!(coords, other_coords);
 /// ```
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Coords {
     /// Zero-based row index, counted from top to bottom.
     pub row: CoordValue,
@@ -53,6 +53,22 @@ impl Coords {
     pub fn is_on_board_with_size(&self, size: CoordValue) -> bool {
         self.row < size && self.column < size
     }
+
+    /// Return the coordinates of this cell after rotating a board of the given size by 180°.
+    ///
+    /// ```
+    /// # use hexgame::Coords;
+    /// assert_eq!(Coords::new(0, 1).rotate_180(5), Coords::new(4, 3));
+    /// ```
+    pub fn rotate_180(&self, size: CoordValue) -> Self {
+        Self::new(size - 1 - self.row, size - 1 - self.column)
+    }
+
+    /// Return the coordinates of this cell after reflecting the board at the long diagonal, i.e. exchange row and column.
+    /// See `Board::transpose_swap_colors`.
+    pub fn transpose(&self) -> Self {
+        Self::new(self.column, self.row)
+    }
 }
 
 impl std::str::FromStr for Coords {
@@ -79,7 +95,7 @@ impl std::str::FromStr for Coords {
 /// Convert a character to a single coordinate (characters are used to denote column indices in the "c4" format).
 /// Return None if `c` is not in the range a-z.
 pub fn parse_column_char(c: char) -> Option<CoordValue> {
-    if ('a'..='z').contains(&c) {
+    if c.is_ascii_lowercase() {
         Some(((c as u8) - b'a') as CoordValue)
     } else {
         None
@@ -88,7 +104,7 @@ pub fn parse_column_char(c: char) -> Option<CoordValue> {
 
 /// Convert a single coordinate (typically the column index) to the character that is used in the "c4" format.
 pub fn to_column_char(column: CoordValue) -> char {
-    (b'a' + (column as u8)) as char
+    (b'a' + column) as char
 }
 
 impl fmt::Display for Coords {
@@ -97,6 +113,22 @@ impl fmt::Display for Coords {
     }
 }
 
+/// With the `serde` feature, coordinates are serialized as strings in "c4" format.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Coords {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Coords {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        string.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 /// Returned by `Coords::from_str` if the string cannot be parsed.
 #[derive(Debug)]
 pub struct ParseCoordsError {
@@ -116,6 +148,15 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[test]
+    fn test_transforms() {
+        let coords = Coords::new(1, 3);
+        assert_eq!(coords.rotate_180(4), Coords::new(2, 0));
+        assert_eq!(coords.rotate_180(4).rotate_180(4), coords);
+        assert_eq!(coords.transpose(), Coords::new(3, 1));
+        assert_eq!(Coords::new(2, 2).rotate_180(5), Coords::new(2, 2));
+    }
+
     #[test]
     fn test_to_string() {
         assert_eq!(Coords::new(0, 0).to_string(), "a1");
@@ -136,4 +177,13 @@ mod tests {
         assert!(Coords::from_str("a0").is_err());
         assert!(Coords::from_str("ä2").is_err());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let coords = Coords::new(12, 5);
+        assert_eq!(serde_json::to_string(&coords).unwrap(), "\"f13\"");
+        assert_eq!(serde_json::from_str::<Coords>("\"f13\"").unwrap(), coords);
+        assert!(serde_json::from_str::<Coords>("\"13f\"").is_err());
+    }
 }
@@ -4,6 +4,8 @@ use crate::{hex_cells::HexCells, Color, Coords};
 ///
 /// The top and bottom edge belong to player `Black`, the other two edges belong to player `White`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Edge {
     Left,
     Top,
@@ -20,7 +22,11 @@ pub fn get_edges_of_color(color: Color) -> [Edge; 2] {
 
 /// Represents either a cell of the board or an edge.
 /// This type is used in a few places that may return both coords and edges (e.g. get_neighbors).
+///
+/// With the `serde` feature, values are serialized as strings like "c4" or "left".
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum CoordsOrEdge {
     Coords(Coords),
     Edge(Edge),
@@ -46,3 +52,19 @@ pub fn set_edge_colors(cells: &mut HexCells) {
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let items: Vec<CoordsOrEdge> = vec![Coords::new(2, 1).into(), Edge::Left.into()];
+        let string = serde_json::to_string(&items).unwrap();
+        assert_eq!(string, r#"["b3","left"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<CoordsOrEdge>>(&string).unwrap(),
+            items
+        );
+    }
+}
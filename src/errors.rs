@@ -3,7 +3,7 @@ use std::error::Error;
 use std::fmt;
 
 /// This enum is returned by `game.play` when the given move is invalid.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum InvalidMove {
     /// The game has ended, thus no further move is possible.
     GameOver,
@@ -11,6 +11,8 @@ pub enum InvalidMove {
     OutOfBounds(Coords),
     /// The player attempted to play on coordinates that are already occupied.
     CellOccupied(Coords),
+    /// The player attempted to swap, but swapping is not allowed (see `Game::swap`).
+    SwapNotAllowed,
 }
 
 impl fmt::Display for InvalidMove {
@@ -23,6 +25,7 @@ impl fmt::Display for InvalidMove {
             InvalidMove::CellOccupied(coords) => {
                 write!(f, "Cell {} is already occupied", coords)
             }
+            InvalidMove::SwapNotAllowed => write!(f, "Swapping is not allowed"),
         }
     }
 }
@@ -40,6 +43,9 @@ pub enum InvalidBoard {
     NotSquare(CoordValue, CoordValue),
     /// When loading a game, no current player was specified, although the game has not yet finished.
     NoCurrentPlayer,
+    /// When parsing a board from text, an unexpected character was found.
+    /// The values contained in this error are: the character and the (one-based) number of the line where it occurred.
+    InvalidCharacter(char, usize),
 }
 
 impl fmt::Display for InvalidBoard {
@@ -58,8 +64,212 @@ impl fmt::Display for InvalidBoard {
             InvalidBoard::NoCurrentPlayer => {
                 write!(f, "No current player given, but game has not yet finished")
             }
+            InvalidBoard::InvalidCharacter(character, line) => {
+                write!(f, "Invalid character '{}' in line {}", character, line)
+            }
         }
     }
 }
 
 impl Error for InvalidBoard {}
+
+/// This error is returned when loading a serialized game or board fails (see `Serialization` and `Game::from_bytes`).
+#[derive(Debug, PartialEq, Eq)]
+pub enum LoadError {
+    /// The input is not valid JSON or does not have the expected structure.
+    /// The values contained in this error are: the message of the JSON parser, and the line and column where the error occurred
+    /// (both one-based, zero if unknown).
+    Json(String, usize, usize),
+    /// The format version is not supported.
+    UnsupportedVersion(u8),
+    /// A color code is invalid.
+    /// The values contained in this error are: the invalid code and the coordinates of the stone, or None if the code does not belong to a stone
+    /// (e.g. the current player).
+    InvalidColor(u8, Option<Coords>),
+    /// The `size` field does not match the stone matrix.
+    /// The values contained in this error are: the value of `size` and the number of rows in the stone matrix.
+    SizeMismatch(CoordValue, usize),
+    /// The stones do not form a valid board.
+    InvalidBoard(InvalidBoard),
+    /// A move in the move list cannot be parsed.
+    /// The values contained in this error are: the index of the move and the unparsable string.
+    UnparsableMove(usize, String),
+    /// A move in the move list is not valid in the current position.
+    /// The values contained in this error are: the index of the move and the reason why the move is invalid.
+    InvalidMove(usize, InvalidMove),
+    /// The stones do not match the position after replaying the move list.
+    StonesDoNotMatchMoves,
+    /// The result of the game is invalid. Contains a description of the problem.
+    InvalidResult(String),
+    /// Binary data has the wrong length.
+    /// The values contained in this error are: the expected length and the actual length in bytes.
+    InvalidLength(usize, usize),
+    /// The unused bits in the last byte of binary cell data are not zero. Contains the last byte.
+    InvalidPadding(u8),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            // The messages of the JSON parser already contain line and column
+            LoadError::Json(message, _, _) => write!(f, "Invalid JSON: {}", message),
+            LoadError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported version: {}", version)
+            }
+            LoadError::InvalidColor(code, Some(coords)) => {
+                write!(f, "Invalid color {} at {}", code, coords)
+            }
+            LoadError::InvalidColor(code, None) => write!(f, "Invalid color {}", code),
+            LoadError::SizeMismatch(size, rows) => write!(
+                f,
+                "Size {} does not match the number of rows {}",
+                size, rows
+            ),
+            LoadError::InvalidBoard(error) => write!(f, "Invalid board: {}", error),
+            LoadError::UnparsableMove(index, string) => {
+                write!(f, "Cannot parse move {} at index {}", string, index)
+            }
+            LoadError::InvalidMove(index, error) => {
+                write!(f, "Invalid move at index {}: {}", index, error)
+            }
+            LoadError::StonesDoNotMatchMoves => {
+                write!(f, "Stones do not match the list of moves")
+            }
+            LoadError::InvalidResult(message) => write!(f, "Invalid result: {}", message),
+            LoadError::InvalidLength(expected, actual) => {
+                write!(f, "Expected {} bytes, but found {}", expected, actual)
+            }
+            LoadError::InvalidPadding(byte) => {
+                write!(
+                    f,
+                    "Unused bits in the last byte {:#010b} are not zero",
+                    byte
+                )
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::InvalidBoard(error) => Some(error),
+            LoadError::InvalidMove(_, error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// This error is returned when parsing a game from trmph or Little Golem notation fails (see `Game::from_trmph`).
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseNotationError {
+    /// The board size is missing, not a number or not bounded by `MIN_BOARD_SIZE` and `MAX_BOARD_SIZE`.
+    /// Contains the invalid size string.
+    InvalidSize(String),
+    /// A token is neither a cell nor a swap.
+    /// The values contained in this error are: the (zero-based) byte position of the token in the input and the token itself.
+    UnrecognizedToken(usize, String),
+    /// A move is not valid in the current position.
+    /// The values contained in this error are: the index of the move and the reason why the move is invalid.
+    InvalidMove(usize, InvalidMove),
+}
+
+impl fmt::Display for ParseNotationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ParseNotationError::InvalidSize(size) => write!(f, "Invalid board size '{}'", size),
+            ParseNotationError::UnrecognizedToken(position, token) => {
+                write!(f, "Unrecognized token '{}' at position {}", token, position)
+            }
+            ParseNotationError::InvalidMove(index, error) => {
+                write!(f, "Invalid move at index {}: {}", index, error)
+            }
+        }
+    }
+}
+
+impl Error for ParseNotationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseNotationError::InvalidMove(_, error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// This error is returned when loading a game tree from SGF fails (see `GameTree::load_sgf`).
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseSgfError {
+    /// The input is not valid SGF.
+    /// The values contained in this error are: the (zero-based) byte position where the problem occurred and a description of the problem.
+    Syntax(usize, String),
+    /// The file does not contain a game of Hex, i.e. the `GM` property is not 11. Contains the value of `GM`.
+    UnsupportedGame(String),
+    /// The value of a property is invalid.
+    /// The values contained in this error are: the byte position of the value, the name of the property and the value.
+    InvalidValue(usize, String, String),
+}
+
+impl fmt::Display for ParseSgfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ParseSgfError::Syntax(position, message) => {
+                write!(f, "Invalid SGF at position {}: {}", position, message)
+            }
+            ParseSgfError::UnsupportedGame(game) => {
+                write!(f, "Unsupported game {}, expected 11 (Hex)", game)
+            }
+            ParseSgfError::InvalidValue(position, property, value) => write!(
+                f,
+                "Invalid value '{}' of property {} at position {}",
+                value, property, position
+            ),
+        }
+    }
+}
+
+impl Error for ParseSgfError {}
+
+/// This error is returned when parsing an opening book fails (see `OpeningBook`).
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseBookError {
+    /// A line does not consist of exactly five fields.
+    /// The values contained in this error are: the (one-based) line number and the number of fields.
+    WrongFieldCount(usize, usize),
+    /// A field or a part of it is invalid, e.g. the size, the player, a cell or a weight.
+    /// The values contained in this error are: the (one-based) line number and the invalid value.
+    InvalidValue(usize, String),
+    /// The position has already been won by one of the players. Contains the (one-based) line number.
+    FinishedPosition(usize),
+}
+
+impl fmt::Display for ParseBookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ParseBookError::WrongFieldCount(line, count) => {
+                write!(f, "Expected 5 fields in line {}, found {}", line, count)
+            }
+            ParseBookError::InvalidValue(line, value) => {
+                write!(f, "Invalid value '{}' in line {}", value, line)
+            }
+            ParseBookError::FinishedPosition(line) => {
+                write!(f, "The position in line {} has already been won", line)
+            }
+        }
+    }
+}
+
+impl Error for ParseBookError {}
+
+impl From<InvalidBoard> for LoadError {
+    fn from(error: InvalidBoard) -> Self {
+        LoadError::InvalidBoard(error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for LoadError {
+    fn from(error: serde_json::Error) -> Self {
+        LoadError::Json(error.to_string(), error.line(), error.column())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::coords::Coords;
+use crate::game::Game;
+
+/// The result of evaluating a position, see `Evaluator`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evaluation {
+    /// Prior probabilities of moves. Moves that are missing get a prior of zero, moves on occupied cells are ignored.
+    /// The priors do not need to be normalized.
+    pub priors: Vec<(Coords, f32)>,
+    /// Expected outcome for the player to move, between -1 (loss) and 1 (win).
+    pub value: f32,
+}
+
+/// A model that evaluates positions for `Puct`, e.g. a neural network.
+///
+/// Only `evaluate` must be implemented. Models that are faster on batches (e.g. on a GPU) should also implement
+/// `evaluate_batch`; `Puct` always evaluates its leaves in batches of `PuctOptions::batch_size`.
+pub trait Evaluator {
+    /// Evaluate the current position of the given game. The game is always ongoing.
+    fn evaluate(&mut self, game: &Game) -> Evaluation;
+
+    /// Evaluate several positions at once and return the evaluations in the same order.
+    fn evaluate_batch(&mut self, games: &[Game]) -> Vec<Evaluation> {
+        games.iter().map(|game| self.evaluate(game)).collect()
+    }
+}
+
+/// An evaluator without any knowledge: All empty cells have the same prior and the value is always 0.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UniformEvaluator;
+
+impl Evaluator for UniformEvaluator {
+    fn evaluate(&mut self, game: &Game) -> Evaluation {
+        Evaluation {
+            priors: game
+                .get_board()
+                .get_empty_cells()
+                .into_iter()
+                .map(|coords| (coords, 1.0))
+                .collect(),
+            value: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniform_evaluator() {
+        let mut game = Game::new(2);
+        game.play(Coords::new(0, 0)).unwrap();
+
+        let evaluations = UniformEvaluator.evaluate_batch(&[Game::new(2), game]);
+
+        assert_eq!(evaluations[0].priors.len(), 4);
+        assert_eq!(evaluations[1].priors.len(), 3);
+        assert!(evaluations[1]
+            .priors
+            .iter()
+            .all(|(coords, prior)| *coords != Coords::new(0, 0) && *prior == 1.0));
+        assert_eq!(evaluations[1].value, 0.0);
+    }
+}
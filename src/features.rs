@@ -0,0 +1,255 @@
+use crate::color::Color;
+use crate::coords::{CoordValue, Coords};
+use crate::game::{Game, Status};
+use crate::moves::Move;
+use crate::symmetry::Symmetry;
+
+const OWN_STONES: usize = 0;
+const OPPONENT_STONES: usize = 1;
+const EMPTY: usize = 2;
+const BLACK_TO_MOVE: usize = 3;
+const OWN_ATTACKED_BRIDGES: usize = 4;
+const OPPONENT_ATTACKED_BRIDGES: usize = 5;
+
+/// Options for `Game::to_features`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FeatureOptions {
+    /// Number of rows and columns added on each side of the board. Padding cells are filled like stones of the player
+    /// owning the adjacent edge, so that networks can see the edges. Cells in the corners of the padding belong to nobody.
+    pub padding: usize,
+    /// Add the two planes for attacked bridges.
+    pub bridge_planes: bool,
+}
+
+impl Default for FeatureOptions {
+    fn default() -> Self {
+        Self {
+            padding: 1,
+            bridge_planes: false,
+        }
+    }
+}
+
+impl FeatureOptions {
+    /// Return the number of planes produced with these options.
+    pub fn plane_count(&self) -> usize {
+        if self.bridge_planes {
+            6
+        } else {
+            4
+        }
+    }
+}
+
+/// Input planes for neural networks, see `Game::to_features`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Features {
+    /// Number of planes.
+    pub plane_count: usize,
+    /// Width and height of each plane, i.e. the board size plus padding on both sides.
+    pub size: usize,
+    /// The values of all planes, plane by plane and row by row (i.e. in "CHW" order).
+    pub data: Vec<f32>,
+    /// The symmetry that has been applied to the position. To map an output of a network (e.g. a policy) back to the board,
+    /// apply the same symmetry to the coordinates (after subtracting the padding).
+    pub symmetry: Symmetry,
+}
+
+impl Features {
+    /// Return the value of the given plane at the given (padded) row and column.
+    pub fn get(&self, plane: usize, row: usize, column: usize) -> f32 {
+        self.data[(plane * self.size + row) * self.size + column]
+    }
+
+    fn set(&mut self, plane: usize, row: usize, column: usize) {
+        self.data[(plane * self.size + row) * self.size + column] = 1.0;
+    }
+}
+
+pub fn extract_features(game: &Game, options: &FeatureOptions) -> Features {
+    let board = game.get_board();
+    // For finished games, use the perspective of the player who would move next.
+    let player = match game.get_status() {
+        Status::Ongoing(color) => color,
+        Status::Finished(winner) => winner.opponent_color(),
+    };
+    let symmetry = match player {
+        Color::Black => Symmetry::Identity,
+        Color::White => Symmetry::TransposeSwapColors,
+    };
+
+    let board_size = board.size() as usize;
+    let padding = options.padding;
+    let size = board_size + 2 * padding;
+    let mut features = Features {
+        plane_count: options.plane_count(),
+        size,
+        data: vec![0.0; options.plane_count() * size * size],
+        symmetry,
+    };
+    let on_board = |index: usize| (padding..padding + board_size).contains(&index);
+
+    for row in 0..size {
+        for column in 0..size {
+            let plane = match (on_board(row), on_board(column)) {
+                (true, true) => {
+                    let coords = Coords::new(
+                        (row - padding) as CoordValue,
+                        (column - padding) as CoordValue,
+                    );
+                    let color = board
+                        .get_color(symmetry.apply_to_coords(coords, board.size()))
+                        .map(|color| symmetry.apply_to_color(color));
+                    Some(match color {
+                        Some(Color::Black) => OWN_STONES,
+                        Some(Color::White) => OPPONENT_STONES,
+                        None => EMPTY,
+                    })
+                }
+                // After normalization, the player to move connects top and bottom.
+                (false, true) => Some(OWN_STONES),
+                (true, false) => Some(OPPONENT_STONES),
+                (false, false) => None,
+            };
+            if let Some(plane) = plane {
+                features.set(plane, row, column);
+            }
+            if player == Color::Black {
+                features.set(BLACK_TO_MOVE, row, column);
+            }
+        }
+    }
+
+    if options.bridge_planes {
+        // The last move was played by the opponent and may attack bridges of the player to move and vice versa.
+        let moves = game.get_moves();
+        let last_moves = [
+            (OWN_ATTACKED_BRIDGES, moves.last()),
+            (OPPONENT_ATTACKED_BRIDGES, moves.iter().rev().nth(1)),
+        ];
+        for (plane, mv) in last_moves {
+            if let Some(Move::Place(coords)) = mv {
+                for cell in board.find_attacked_bridges(*coords) {
+                    // The bridge may have been repaired already.
+                    if board.get_color(cell).is_none() {
+                        let cell = symmetry.apply_to_coords(cell, board.size());
+                        features.set(
+                            plane,
+                            cell.row as usize + padding,
+                            cell.column as usize + padding,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_board() {
+        let features = extract_features(&Game::new(3), &FeatureOptions::default());
+
+        assert_eq!(features.plane_count, 4);
+        assert_eq!(features.size, 5);
+        assert_eq!(features.data.len(), 4 * 5 * 5);
+        assert_eq!(features.symmetry, Symmetry::Identity);
+        // Top and bottom padding belong to Black, left and right padding to White.
+        assert_eq!(features.get(OWN_STONES, 0, 2), 1.0);
+        assert_eq!(features.get(OWN_STONES, 4, 1), 1.0);
+        assert_eq!(features.get(OPPONENT_STONES, 2, 0), 1.0);
+        assert_eq!(features.get(OPPONENT_STONES, 3, 4), 1.0);
+        // Corners belong to nobody
+        assert_eq!(features.get(OWN_STONES, 0, 0), 0.0);
+        assert_eq!(features.get(OPPONENT_STONES, 0, 0), 0.0);
+        assert_eq!(features.get(EMPTY, 0, 0), 0.0);
+        assert_eq!(features.get(EMPTY, 2, 2), 1.0);
+        assert!(features.data[BLACK_TO_MOVE * 25..]
+            .iter()
+            .all(|x| *x == 1.0));
+    }
+
+    #[test]
+    fn test_without_padding() {
+        let mut game = Game::new(2);
+        game.play(Coords::new(0, 1)).unwrap();
+        let options = FeatureOptions {
+            padding: 0,
+            bridge_planes: false,
+        };
+
+        let features = extract_features(&game, &options);
+
+        // White to move, so the board is transposed and the black stone is an opponent stone at (1, 0).
+        assert_eq!(features.symmetry, Symmetry::TransposeSwapColors);
+        #[rustfmt::skip]
+        assert_eq!(features.data, vec![
+            0.0, 0.0, 0.0, 0.0, // own stones
+            0.0, 0.0, 1.0, 0.0, // opponent stones
+            1.0, 1.0, 0.0, 1.0, // empty
+            0.0, 0.0, 0.0, 0.0, // Black to move
+        ]);
+    }
+
+    #[test]
+    fn test_white_is_presented_like_black() {
+        // Equivalent positions: The second one is the first one transposed with swapped colors.
+        let black_game = Game::load(
+            vec![
+                vec![None, None, Some(Color::Black)],
+                vec![Some(Color::White), None, None],
+                vec![None, None, None],
+            ],
+            Some(Color::Black),
+        )
+        .unwrap();
+        let white_game = Game::load(
+            vec![
+                vec![None, Some(Color::Black), None],
+                vec![None, None, None],
+                vec![Some(Color::White), None, None],
+            ],
+            Some(Color::White),
+        )
+        .unwrap();
+
+        let black_features = extract_features(&black_game, &FeatureOptions::default());
+        let white_features = extract_features(&white_game, &FeatureOptions::default());
+
+        // Only the side-to-move plane differs.
+        let length = BLACK_TO_MOVE * 5 * 5;
+        assert_eq!(black_features.data[..length], white_features.data[..length]);
+        assert_ne!(black_features.data[length..], white_features.data[length..]);
+        assert_eq!(white_features.symmetry, Symmetry::TransposeSwapColors);
+    }
+
+    #[test]
+    fn test_bridge_planes() {
+        // Black has a bridge between b1 and a3 (via a2 and b2). White attacks it on b2.
+        let mut game = Game::new(3);
+        game.play(Coords::new(0, 1)).unwrap();
+        game.play(Coords::new(2, 2)).unwrap();
+        game.play(Coords::new(2, 0)).unwrap();
+        game.play(Coords::new(1, 1)).unwrap();
+        let options = FeatureOptions {
+            padding: 0,
+            bridge_planes: true,
+        };
+
+        let features = extract_features(&game, &options);
+
+        assert_eq!(features.plane_count, 6);
+        assert_eq!(features.get(OWN_ATTACKED_BRIDGES, 1, 0), 1.0);
+        assert_eq!(
+            features.data[OWN_ATTACKED_BRIDGES * 9..OPPONENT_ATTACKED_BRIDGES * 9]
+                .iter()
+                .sum::<f32>(),
+            1.0
+        );
+    }
+}
@@ -1,8 +1,66 @@
 use std::fmt;
 
-use crate::board::Board;
+use crate::board::{Board, StoneMatrix};
 use crate::color::Color;
 use crate::coords::{to_column_char, CoordValue, Coords};
+use crate::errors::InvalidBoard;
+
+/// Layout used by `RenderOptions`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// Each row is indented by one more space than the previous one, so that cells form a rhombus (see `Board`).
+    #[default]
+    Slanted,
+    /// All rows start at the same column, so that cells form a square.
+    Flat,
+}
+
+/// Options for rendering a board with `Board::render`.
+///
+/// The default options produce the same output as `Display`.
+///
+/// ```
+/// # use hexgame::{Board, Color, Coords, Layout, RenderOptions};
+/// let mut board = Board::new(3);
+/// board.play(Coords::new(1, 1), Color::Black).unwrap();
+///
+/// let options = RenderOptions {
+///     ascii: true,
+///     zero_based: true,
+///     layout: Layout::Flat,
+///     last_move: Some(Coords::new(1, 1)),
+///     ..RenderOptions::default()
+/// };
+/// assert_eq!(board.render(&options), concat!(
+///     "   0  1  2 \n",
+///     "0  .  .  .  0\n",
+///     "1  . (x) .  1\n",
+///     "2  .  .  .  2\n",
+///     "   0  1  2 \n",
+/// ));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Use `x` for Black and `o` for White instead of ● and ○.
+    pub ascii: bool,
+    /// Use ANSI escape codes to color the stones and to highlight cells. Otherwise cells are highlighted by brackets.
+    pub ansi_colors: bool,
+    /// Label rows and columns with zero-based numbers as in the `Coords` docs instead of letters and one-based numbers.
+    pub zero_based: bool,
+    /// Layout of the rows.
+    pub layout: Layout,
+    /// The last move, which is marked by parentheses (or inverted colors with `ansi_colors`).
+    pub last_move: Option<Coords>,
+    /// Further cells to highlight, e.g. a winning path or attacked bridges. They are marked by square brackets
+    /// (or a yellow background with `ansi_colors`).
+    pub highlighted: Vec<Coords>,
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_BLACK_STONE: &str = "1;31";
+const ANSI_WHITE_STONE: &str = "1;34";
+const ANSI_LAST_MOVE: &str = "7";
+const ANSI_HIGHLIGHTED: &str = "43";
 
 impl fmt::Display for Board {
     /// Pretty human-readable format for boards.
@@ -17,47 +75,148 @@ impl fmt::Display for Board {
     ///     5\.  .  .  .  .\5
     ///        a  b  c  d  e
     /// ```
+    ///
+    /// Use `Board::render` for more options.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write_column_labels(f, self.size(), 0)?;
+        write_board(f, self, &RenderOptions::default())
+    }
+}
 
-        for row in 0..self.size() {
-            write_row(f, self, row)?;
-        }
+pub fn render(board: &Board, options: &RenderOptions) -> String {
+    let mut output = String::new();
+    write_board(&mut output, board, options).expect("Writing to a string cannot fail");
+    output
+}
+
+fn write_board(f: &mut impl fmt::Write, board: &Board, options: &RenderOptions) -> fmt::Result {
+    // Without ANSI colors, highlighted cells need a character on each side for the brackets.
+    let markers =
+        !options.ansi_colors && (options.last_move.is_some() || !options.highlighted.is_empty());
+    let marker_indent = markers as usize;
+    let label_width = row_label(board.size() - 1, options).len();
+
+    let (top_indent, bottom_indent) = match options.layout {
+        Layout::Slanted => (0, board.size() as usize + 1),
+        Layout::Flat => (label_width, label_width),
+    };
 
-        write_column_labels(f, self.size(), (self.size() + 1) as usize)
+    write_column_labels(f, board.size(), top_indent + marker_indent, options)?;
+
+    for row in 0..board.size() {
+        write_row(f, board, row, markers, label_width, options)?;
     }
+
+    write_column_labels(f, board.size(), bottom_indent + marker_indent, options)
 }
 
 fn write_column_labels(
-    f: &mut fmt::Formatter,
+    f: &mut impl fmt::Write,
     board_size: CoordValue,
     indent: usize,
+    options: &RenderOptions,
 ) -> fmt::Result {
     write_indent(f, indent)?;
 
     for column in 0..board_size {
-        write!(f, " {} ", to_column_char(column))?;
+        if options.zero_based {
+            // Right-align numbers, so that the last digit is above the cell, like the letters.
+            write!(f, "{:>2} ", column)?;
+        } else {
+            write!(f, " {} ", to_column_char(column))?;
+        }
     }
 
     writeln!(f)
 }
 
-fn write_row(f: &mut fmt::Formatter, board: &Board, row: CoordValue) -> fmt::Result {
-    write_indent(f, row as usize)?;
-    write!(f, "{}\\", row + 1)?;
+fn write_row(
+    f: &mut impl fmt::Write,
+    board: &Board,
+    row: CoordValue,
+    markers: bool,
+    label_width: usize,
+    options: &RenderOptions,
+) -> fmt::Result {
+    let label = row_label(row, options);
+    match options.layout {
+        Layout::Slanted => {
+            write_indent(f, row as usize)?;
+            write!(f, "{}\\", label)?;
+        }
+        Layout::Flat => write!(f, "{:>width$} ", label, width = label_width)?,
+    }
 
     for column in 0..board.size() {
-        if column > 0 {
-            write!(f, "  ")?;
-        }
-        let color = board.get_color(Coords { row, column });
-        write!(f, "{}", char_for_color(color))?;
+        let coords = Coords { row, column };
+        let (left, right) = if !markers {
+            if column > 0 {
+                write!(f, "  ")?;
+            }
+            ("", "")
+        } else if options.last_move == Some(coords) {
+            ("(", ")")
+        } else if options.highlighted.contains(&coords) {
+            ("[", "]")
+        } else {
+            (" ", " ")
+        };
+
+        write!(f, "{}", left)?;
+        write_cell(f, board, coords, options)?;
+        write!(f, "{}", right)?;
     }
 
-    writeln!(f, "\\{}", row + 1)
+    match options.layout {
+        Layout::Slanted => writeln!(f, "\\{}", label),
+        Layout::Flat => writeln!(f, " {}", label),
+    }
 }
 
-fn write_indent(f: &mut fmt::Formatter, length: usize) -> fmt::Result {
+fn write_cell(
+    f: &mut impl fmt::Write,
+    board: &Board,
+    coords: Coords,
+    options: &RenderOptions,
+) -> fmt::Result {
+    let color = board.get_color(coords);
+    let symbol = if options.ascii {
+        ascii_char_for_color(color)
+    } else {
+        char_for_color(color)
+    };
+
+    if !options.ansi_colors {
+        return write!(f, "{}", symbol);
+    }
+
+    let mut codes = vec![];
+    match color {
+        Some(Color::Black) => codes.push(ANSI_BLACK_STONE),
+        Some(Color::White) => codes.push(ANSI_WHITE_STONE),
+        None => {}
+    }
+    if options.last_move == Some(coords) {
+        codes.push(ANSI_LAST_MOVE);
+    } else if options.highlighted.contains(&coords) {
+        codes.push(ANSI_HIGHLIGHTED);
+    }
+
+    if codes.is_empty() {
+        write!(f, "{}", symbol)
+    } else {
+        write!(f, "\x1b[{}m{}{}", codes.join(";"), symbol, ANSI_RESET)
+    }
+}
+
+fn row_label(row: CoordValue, options: &RenderOptions) -> String {
+    if options.zero_based {
+        row.to_string()
+    } else {
+        (row + 1).to_string()
+    }
+}
+
+fn write_indent(f: &mut impl fmt::Write, length: usize) -> fmt::Result {
     write!(f, "{}", " ".repeat(length))
 }
 
@@ -71,6 +230,89 @@ fn char_for_color(color: Option<Color>) -> char {
     }
 }
 
+fn ascii_char_for_color(color: Option<Color>) -> char {
+    match color {
+        Some(Color::Black) => 'x',
+        Some(Color::White) => 'o',
+        None => '.',
+    }
+}
+
+impl std::str::FromStr for Board {
+    type Err = InvalidBoard;
+
+    /// Parse a board from the format produced by `Display` (see `Board`).
+    ///
+    /// The parser is lenient: Labels and indentation are optional and the ASCII characters `x`/`X` (Black) and `o`/`O` (White)
+    /// may be used instead of ● and ○. Empty cells must be written as `.`.
+    ///
+    /// ```
+    /// # use hexgame::{Board, Color, Coords};
+    /// let board: Board = "
+    ///  a  b  c
+    /// 1\\.  x  .\\1
+    ///  2\\.  ○  .\\2
+    ///   3\\.  .  .\\3
+    ///      a  b  c
+    /// ".parse().unwrap();
+    /// assert_eq!(board.get_color(Coords::new(0, 1)), Some(Color::Black));
+    /// assert_eq!(board.get_color(Coords::new(1, 1)), Some(Color::White));
+    ///
+    /// let board: Board = "x . .\n . o .\n  . . .".parse().unwrap();
+    /// assert_eq!(board.size(), 3);
+    /// ```
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut stones: StoneMatrix = vec![];
+
+        for (line_index, line) in string.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || is_column_label_line(line) {
+                continue;
+            }
+
+            let cells = strip_row_labels(line);
+            let row = cells
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| parse_cell(c).ok_or(InvalidBoard::InvalidCharacter(c, line_index + 1)))
+                .collect::<Result<_, _>>()?;
+            stones.push(row);
+        }
+
+        Board::from_stone_matrix(stones)
+    }
+}
+
+/// Column labels are either letters (a, b, c, ...) or zero-based numbers (0, 1, 2, ...).
+fn is_column_label_line(line: &str) -> bool {
+    let letters = line
+        .split_whitespace()
+        .zip(0..)
+        .all(|(label, column)| label.len() == 1 && label.starts_with(to_column_char(column)));
+    let numbers = line
+        .split_whitespace()
+        .zip(0..)
+        .all(|(label, column): (&str, CoordValue)| label == column.to_string());
+    letters || numbers
+}
+
+/// Remove row labels like "12\\" at the beginning and "\\12" at the end of a line.
+fn strip_row_labels(line: &str) -> &str {
+    let line = line.trim_start_matches(|c: char| c.is_ascii_digit());
+    let line = line.strip_prefix('\\').unwrap_or(line);
+    let line = line.trim_end_matches(|c: char| c.is_ascii_digit());
+    line.strip_suffix('\\').unwrap_or(line)
+}
+
+fn parse_cell(c: char) -> Option<Option<Color>> {
+    match c {
+        '●' | 'x' | 'X' => Some(Some(Color::Black)),
+        '○' | 'o' | 'O' => Some(Some(Color::White)),
+        '.' => Some(None),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -94,4 +336,194 @@ mod test {
             "     a  b  c \n",
         ));
     }
+
+    #[test]
+    fn test_render_default_matches_display() {
+        let mut board = Board::new(4);
+        board.play(Coords::new(1, 2), Color::Black).ok();
+        assert_eq!(board.render(&RenderOptions::default()), board.to_string());
+    }
+
+    #[test]
+    fn test_render_ascii_zero_based() {
+        let mut board = Board::new(2);
+        board.play(Coords::new(0, 1), Color::Black).ok();
+        board.play(Coords::new(1, 0), Color::White).ok();
+
+        let options = RenderOptions {
+            ascii: true,
+            zero_based: true,
+            ..RenderOptions::default()
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(board.render(&options), concat!(
+            " 0  1 \n",
+            "0\\.  x\\0\n",
+            " 1\\o  .\\1\n",
+            "    0  1 \n",
+        ));
+    }
+
+    #[test]
+    fn test_render_highlighted_cells() {
+        let mut board = Board::new(3);
+        board.play(Coords::new(0, 0), Color::Black).ok();
+        board.play(Coords::new(1, 0), Color::Black).ok();
+
+        let options = RenderOptions {
+            last_move: Some(Coords::new(1, 0)),
+            highlighted: vec![Coords::new(0, 0), Coords::new(2, 2)],
+            ..RenderOptions::default()
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(board.render(&options), concat!(
+            "  a  b  c \n",
+            "1\\[●] .  . \\1\n",
+            " 2\\(●) .  . \\2\n",
+            "  3\\ .  . [.]\\3\n",
+            "      a  b  c \n",
+        ));
+    }
+
+    #[test]
+    fn test_render_ansi_colors() {
+        let mut board = Board::new(2);
+        board.play(Coords::new(0, 0), Color::Black).ok();
+        board.play(Coords::new(1, 1), Color::White).ok();
+
+        let options = RenderOptions {
+            ansi_colors: true,
+            layout: Layout::Flat,
+            last_move: Some(Coords::new(1, 1)),
+            highlighted: vec![Coords::new(0, 1)],
+            ..RenderOptions::default()
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(board.render(&options), concat!(
+            "  a  b \n",
+            "1 \x1b[1;31m●\x1b[0m  \x1b[43m.\x1b[0m 1\n",
+            "2 .  \x1b[1;34;7m○\x1b[0m 2\n",
+            "  a  b \n",
+        ));
+    }
+
+    #[test]
+    fn test_render_flat_layout_with_wide_labels() {
+        let board = Board::new(10);
+        let output = board.render(&RenderOptions {
+            layout: Layout::Flat,
+            ..RenderOptions::default()
+        });
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[1], " 1 .  .  .  .  .  .  .  .  .  . 1");
+        assert_eq!(lines[10], "10 .  .  .  .  .  .  .  .  .  . 10");
+    }
+
+    #[test]
+    fn test_render_zero_based_labels_on_large_board() {
+        let board = Board::new(11);
+        for layout in [Layout::Slanted, Layout::Flat] {
+            let output = board.render(&RenderOptions {
+                zero_based: true,
+                layout,
+                ..RenderOptions::default()
+            });
+            let lines: Vec<&str> = output.lines().collect();
+
+            // The offset between each label and the cells of the adjacent row must be the same for all columns
+            // (in the slanted layout, labels are shifted by half a cell).
+            for (labels, cells) in [(lines[0], lines[1]), (lines[12], lines[11])] {
+                let label_ends: Vec<usize> = labels
+                    .match_indices(|c: char| c.is_ascii_digit())
+                    .map(|(index, _)| index)
+                    .filter(|index| !labels[index + 1..].starts_with(|c: char| c.is_ascii_digit()))
+                    .collect();
+                let cell_positions: Vec<usize> =
+                    cells.match_indices('.').map(|(index, _)| index).collect();
+                let offsets: Vec<isize> = label_ends
+                    .iter()
+                    .zip(cell_positions.iter())
+                    .map(|(label, cell)| *label as isize - *cell as isize)
+                    .collect();
+                assert_eq!(offsets.len(), 11);
+                assert!(
+                    offsets.iter().all(|offset| *offset == offsets[0]),
+                    "{:?}",
+                    layout
+                );
+            }
+        }
+
+        let output = board.render(&RenderOptions {
+            zero_based: true,
+            layout: Layout::Flat,
+            ..RenderOptions::default()
+        });
+        assert_eq!(
+            output.lines().next().unwrap(),
+            "   0  1  2  3  4  5  6  7  8  9 10 "
+        );
+    }
+
+    #[test]
+    fn test_parse_display_output() {
+        let mut board = Board::new(11);
+        board.play(Coords { row: 0, column: 0 }, Color::Black).ok();
+        board.play(Coords { row: 10, column: 3 }, Color::White).ok();
+        board.play(Coords { row: 9, column: 10 }, Color::Black).ok();
+
+        let parsed_board: Board = board.to_string().parse().unwrap();
+
+        assert_eq!(parsed_board.to_stone_matrix(), board.to_stone_matrix());
+    }
+
+    #[test]
+    fn test_parse_without_labels() {
+        let board: Board = "\n  x  .\n   .  o\n".parse().unwrap();
+        assert_eq!(
+            board.to_stone_matrix(),
+            vec![
+                vec![Some(Color::Black), None],
+                vec![None, Some(Color::White)]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_zero_based_labels() {
+        #[rustfmt::skip]
+        let string = concat!(
+            " 0  1\n",
+            "0\\.  ●\\0\n",
+            " 1\\○  .\\1\n",
+            "    0  1\n",
+        );
+        let board: Board = string.parse().unwrap();
+        assert_eq!(
+            board.to_stone_matrix(),
+            vec![
+                vec![None, Some(Color::Black)],
+                vec![Some(Color::White), None]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_character() {
+        let result = "x .\n. #".parse::<Board>();
+        assert_eq!(
+            result.err().unwrap(),
+            InvalidBoard::InvalidCharacter('#', 2)
+        );
+    }
+
+    #[test]
+    fn test_parse_non_square_board() {
+        let result = ". .\n. . .".parse::<Board>();
+        assert_eq!(result.err().unwrap(), InvalidBoard::NotSquare(1, 2));
+    }
 }
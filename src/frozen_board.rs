@@ -0,0 +1,186 @@
+use crate::board::{Board, StoneMatrix};
+use crate::color::Color;
+use crate::coords::{CoordValue, Coords};
+use crate::edges::{CoordsOrEdge, Edge};
+use crate::hex_cells::{HexCells, Index};
+use crate::union_find::UnionFind;
+
+/// An immutable snapshot of a `Board` that can be shared between threads.
+///
+/// `Board` is not `Sync`, because `is_in_same_set` compresses the paths of its union-find structure through a shared
+/// reference. A frozen board stores the root of each cell instead, so connection queries are a simple comparison and
+/// need no mutation at all. Use `Board::freeze` to create a frozen board and `to_board` to continue playing.
+///
+/// ```
+/// # use hexgame::{Board, Color, Coords, Edge};
+/// let mut board = Board::new(3);
+/// board.play(Coords::new(0, 1), Color::Black).unwrap();
+/// let frozen = board.freeze();
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| assert!(frozen.is_in_same_set(Coords::new(0, 1), Edge::Top)));
+///     scope.spawn(|| assert!(!frozen.is_in_same_set(Coords::new(0, 1), Edge::Bottom)));
+/// });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrozenBoard {
+    size: CoordValue,
+    // Same layout as `HexCells`: cells row by row, followed by the edges
+    colors: Vec<Option<Color>>,
+    roots: Vec<Index>,
+}
+
+impl FrozenBoard {
+    pub(crate) fn new(cells: &HexCells) -> Self {
+        let count = cells.index_from_edge(Edge::Bottom) + 1;
+        Self {
+            size: cells.size,
+            colors: (0..count)
+                .map(|index| cells.get_color_at_index(index))
+                .collect(),
+            roots: (0..count).map(|index| cells.find_root(index)).collect(),
+        }
+    }
+
+    /// Return the size of this board. Boards are always square.
+    pub fn size(&self) -> CoordValue {
+        self.size
+    }
+
+    /// Return the color at the given coordinates or edge, see `Board::get_color`.
+    pub fn get_color<T: Into<CoordsOrEdge>>(&self, coords_or_edge: T) -> Option<Color> {
+        self.colors[self.index(coords_or_edge.into())]
+    }
+
+    /// Check whether the given cells or edges are connected by stones of the same color, see `Board::is_in_same_set`.
+    pub fn is_in_same_set<S: Into<CoordsOrEdge>, T: Into<CoordsOrEdge>>(&self, s: S, t: T) -> bool {
+        self.roots[self.index(s.into())] == self.roots[self.index(t.into())]
+    }
+
+    /// Return all empty cells.
+    pub fn get_empty_cells(&self) -> Vec<Coords> {
+        (0..self.size)
+            .flat_map(|row| (0..self.size).map(move |column| Coords { row, column }))
+            .filter(|coords| self.get_color(*coords).is_none())
+            .collect()
+    }
+
+    /// Convert this board to a `StoneMatrix`.
+    pub fn to_stone_matrix(&self) -> StoneMatrix {
+        (0..self.size)
+            .map(|row| {
+                (0..self.size)
+                    .map(|column| self.get_color(Coords::new(row, column)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Create a normal (mutable) board with the same stones.
+    pub fn to_board(&self) -> Board {
+        Board::from_stone_matrix(self.to_stone_matrix()).expect("Frozen boards are valid")
+    }
+
+    fn index(&self, coords_or_edge: CoordsOrEdge) -> usize {
+        let size = self.size as usize;
+        match coords_or_edge {
+            CoordsOrEdge::Coords(coords) => {
+                debug_assert!(coords.is_on_board_with_size(self.size));
+                coords.row as usize * size + coords.column as usize
+            }
+            CoordsOrEdge::Edge(edge) => {
+                size * size
+                    + match edge {
+                        Edge::Left => 0,
+                        Edge::Top => 1,
+                        Edge::Right => 2,
+                        Edge::Bottom => 3,
+                    }
+            }
+        }
+    }
+}
+
+impl From<&Board> for FrozenBoard {
+    fn from(board: &Board) -> Self {
+        board.freeze()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Board {
+        // Black connects top and bottom via b1, a2, a3. White has two separate stones.
+        "
+            .  ●  ○
+            ●  .  .
+            ●  ○  .
+        "
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_frozen_board_is_sync() {
+        fn assert_sync<T: Sync + Send>() {}
+        assert_sync::<FrozenBoard>();
+    }
+
+    #[test]
+    fn test_same_queries_as_board() {
+        let board = board();
+        let frozen = board.freeze();
+
+        assert_eq!(frozen.size(), 3);
+        let mut items: Vec<CoordsOrEdge> = vec![
+            Edge::Left.into(),
+            Edge::Top.into(),
+            Edge::Right.into(),
+            Edge::Bottom.into(),
+        ];
+        for row in 0..3 {
+            for column in 0..3 {
+                items.push(Coords::new(row, column).into());
+            }
+        }
+        for s in &items {
+            assert_eq!(frozen.get_color(*s), board.get_color(*s));
+            for t in &items {
+                assert_eq!(
+                    frozen.is_in_same_set(*s, *t),
+                    board.is_in_same_set(*s, *t),
+                    "{:?} {:?}",
+                    s,
+                    t
+                );
+            }
+        }
+        assert!(frozen.is_in_same_set(Edge::Top, Edge::Bottom));
+        assert_eq!(frozen.get_empty_cells(), board.get_empty_cells());
+    }
+
+    #[test]
+    fn test_to_board() {
+        let board = board();
+        let mut thawed = FrozenBoard::from(&board).to_board();
+
+        assert_eq!(thawed.to_stone_matrix(), board.to_stone_matrix());
+        thawed.play(Coords::new(1, 1), Color::White).unwrap();
+        assert!(thawed.is_in_same_set(Coords::new(0, 2), Coords::new(2, 1)));
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let frozen = board().freeze();
+        let results: Vec<bool> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| frozen.is_in_same_set(Edge::Top, Edge::Bottom)))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(results, vec![true; 4]);
+    }
+}
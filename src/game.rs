@@ -1,11 +1,23 @@
+use crate::binary::{decode_game, encode_game};
 use crate::board::{Board, StoneMatrix};
 use crate::color::Color;
 use crate::coords::{CoordValue, Coords};
 use crate::edges::get_edges_of_color;
-use crate::errors::{InvalidBoard, InvalidMove};
+use crate::errors::{InvalidBoard, InvalidMove, LoadError, ParseNotationError};
+use crate::features::{extract_features, FeatureOptions, Features};
+use crate::metadata::GameMetadata;
+use crate::moves::Move;
+use crate::notation::{parse_little_golem, parse_trmph, to_little_golem, to_trmph};
+use crate::solver::Solution;
+use crate::svg::{game_to_animated_svg, game_to_svg, game_to_svg_frames, SvgOptions};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Status of a game.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Status {
     /// Game has not yet ended. The `Color` indicates which player is to make the next turn.
     Ongoing(Color),
@@ -14,13 +26,100 @@ pub enum Status {
     Finished(Color),
 }
 
+/// The reason why a game has ended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum WinReason {
+    /// The winner has connected their edges.
+    Connection,
+    /// The loser has resigned.
+    Resignation,
+    /// The loser has run out of time.
+    Timeout,
+    /// The loser has forfeited the game, e.g. by repeatedly attempting illegal moves.
+    Forfeit,
+}
+
+/// Result of a finished game: Who has won and why.
+///
+/// `GameResult` implements `Display` to produce messages like "White wins by resignation".
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameResult {
+    /// The player who has won the game.
+    pub winner: Color,
+    /// The reason why the game has ended.
+    pub reason: WinReason,
+}
+
+impl GameResult {
+    /// Create a new result.
+    pub fn new(winner: Color, reason: WinReason) -> Self {
+        Self { winner, reason }
+    }
+
+    /// Return the player who has lost the game.
+    pub fn loser(&self) -> Color {
+        self.winner.opponent_color()
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.reason {
+            WinReason::Connection => "by connection",
+            WinReason::Resignation => "by resignation",
+            WinReason::Timeout => "on time",
+            WinReason::Forfeit => "by forfeit",
+        };
+        write!(f, "{} wins {}", self.winner, reason)
+    }
+}
+
+/// The position a game has been loaded from, see `Game::get_setup`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Setup {
+    /// The stones on the board when the game was loaded.
+    pub stones: StoneMatrix,
+    /// The player to move when the game was loaded, or None if the game had already ended.
+    pub current_player: Option<Color>,
+}
+
 /// `Game` holds the full state of a game of Hex and allows to manipulate this state by playing valid moves.
 ///
 /// The game state consists of a board (`get_board`) and the current player (`get_current_player`).
+/// Once the game has ended, `get_result` tells who has won and why.
+/// Additionally, games may carry metadata like player names (`get_metadata`).
+///
+/// Games keep track of all moves (`get_moves`). If the game was not started on an empty board, but loaded from a position
+/// (see `load`), the moves are relative to that position (`get_setup`).
+///
+/// # The Swap Rule
+///
+/// If the swap rule is enabled in the metadata, White may answer Black's first move with `swap`:
+/// Black's stone is replaced by a white stone mirrored at the long diagonal and Black moves next.
+/// ```
+/// # use hexgame::{Color, Coords, Game};
+/// let mut game = Game::new(5);
+/// game.get_metadata_mut().swap_rule = true;
+/// game.play(Coords::new(1, 3)).unwrap();
+/// game.swap().unwrap();
+///
+/// assert_eq!(game.get_board().get_color(Coords::new(3, 1)), Some(Color::White));
+/// assert_eq!(game.get_current_player(), Some(Color::Black));
+/// ```
 #[derive(Clone)]
 pub struct Game {
     board: Board,
     status: Status,
+    result: Option<GameResult>,
+    moves: Vec<Move>,
+    // Setup and metadata are shared between clones so that cloning games (e.g. in tree search) stays cheap.
+    setup: Option<Arc<Setup>>,
+    metadata: Option<Arc<GameMetadata>>,
 }
 
 impl Game {
@@ -33,6 +132,10 @@ impl Game {
         Game {
             board: Board::new(size),
             status: Status::Ongoing(Color::Black),
+            result: None,
+            moves: vec![],
+            setup: None,
+            metadata: None,
         }
     }
 
@@ -54,6 +157,42 @@ impl Game {
         self.status
     }
 
+    /// Return the result of the game, or None if the game has not yet ended.
+    pub fn get_result(&self) -> Option<GameResult> {
+        self.result
+    }
+
+    /// Return all moves that have been played in this game.
+    ///
+    /// If the game has been loaded from a position (see `get_setup`), only the moves played after loading are returned.
+    pub fn get_moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Return the position this game has been loaded from, or None if the game was started on an empty board.
+    pub fn get_setup(&self) -> Option<&Setup> {
+        self.setup.as_deref()
+    }
+
+    /// Return the game's metadata (player names, event, etc.).
+    pub fn get_metadata(&self) -> &GameMetadata {
+        self.metadata.as_deref().unwrap_or(&GameMetadata::EMPTY)
+    }
+
+    /// Return the game's metadata for modification.
+    pub fn get_metadata_mut(&mut self) -> &mut GameMetadata {
+        Arc::make_mut(self.metadata.get_or_insert_with(Default::default))
+    }
+
+    /// Replace the game's metadata.
+    pub fn set_metadata(&mut self, metadata: GameMetadata) {
+        self.metadata = if metadata.is_empty() {
+            None
+        } else {
+            Some(Arc::new(metadata))
+        };
+    }
+
     /// Load a game from a `StoneMatrix` and a current player color.
     ///
     /// This method returns an error if `current_player` is None, but the game has not yet finished.
@@ -61,9 +200,156 @@ impl Game {
     ///
     /// Please also have a look at the `Serialization` trait which allows to directly deserialize a game from JSON.
     pub fn load(stones: StoneMatrix, current_player: Option<Color>) -> Result<Self, InvalidBoard> {
-        let mut board = Board::from_stone_matrix(stones)?;
+        let mut board = Board::from_stone_matrix(stones.clone())?;
         let status = Self::compute_status(&mut board, current_player)?;
-        Ok(Self { board, status })
+        let (result, current_player) = match status {
+            Status::Ongoing(color) => (None, Some(color)),
+            Status::Finished(winner) => {
+                (Some(GameResult::new(winner, WinReason::Connection)), None)
+            }
+        };
+        Ok(Self {
+            board,
+            status,
+            result,
+            moves: vec![],
+            setup: Some(Arc::new(Setup {
+                stones,
+                current_player,
+            })),
+            metadata: None,
+        })
+    }
+
+    /// Return this game as it was before any of its moves were played, i.e. the setup (or an empty board) with the same metadata.
+    pub(crate) fn initial_position(&self) -> Game {
+        let mut game = match &self.setup {
+            Some(setup) => Game::load(setup.stones.clone(), setup.current_player)
+                .expect("The setup was valid when the game was loaded"),
+            None => Game::new(self.board.size()),
+        };
+        game.metadata = self.metadata.clone();
+        game
+    }
+
+    /// Encode the current position of this game in a compact binary format, e.g. for training datasets:
+    /// One byte for the size, one byte for the status (the current player: 0 if the game has ended, 1 for Black,
+    /// 2 for White; for finished games also the winner and the `WinReason`), followed by 2 bits per cell (row by row).
+    ///
+    /// Only the position and the result are encoded, not the moves or metadata. Use `PositionWriter` to write many positions to a file.
+    /// ```
+    /// # use hexgame::{Coords, Game};
+    /// let mut game = Game::new(11);
+    /// game.play(Coords::new(5, 5)).unwrap();
+    /// let bytes = game.to_bytes();
+    /// assert_eq!(bytes.len(), 33);
+    ///
+    /// let loaded_game = Game::from_bytes(&bytes).unwrap();
+    /// assert_eq!(loaded_game.get_board().to_stone_matrix(), game.get_board().to_stone_matrix());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_game(self)
+    }
+
+    /// Decode a game from the format produced by `to_bytes`. The game is loaded like in `load`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LoadError> {
+        decode_game(bytes)
+    }
+
+    /// Parse a game from trmph notation as used in URLs of trmph.com, e.g. "https://trmph.com/hex/board#11,a1b2c3".
+    ///
+    /// The URL is optional ("11,a1b2c3" works, too). A swap is written by repeating the first cell, e.g. "11,a1a1".
+    /// If the game contains a swap, the swap rule is enabled in the game's metadata.
+    /// ```
+    /// # use hexgame::{Coords, Game, Move};
+    /// let game = Game::from_trmph("https://trmph.com/hex/board#11,f6f6a1").unwrap();
+    /// assert_eq!(game.get_moves()[1], Move::Swap);
+    /// assert_eq!(game.to_trmph(), "https://trmph.com/hex/board#11,f6f6a1");
+    /// ```
+    pub fn from_trmph(string: &str) -> Result<Self, ParseNotationError> {
+        parse_trmph(string)
+    }
+
+    /// Return the moves of this game in trmph notation (see `from_trmph`).
+    /// The setup of a loaded game cannot be represented and is ignored.
+    pub fn to_trmph(&self) -> String {
+        to_trmph(self)
+    }
+
+    /// Parse the moves of a game from Little Golem notation, where cells are written as two letters (column and row)
+    /// and moves are separated by whitespace, e.g. "cd swap ef" for c4, swap, e6.
+    ///
+    /// If the game contains a swap, the swap rule is enabled in the game's metadata.
+    pub fn from_little_golem(size: CoordValue, string: &str) -> Result<Self, ParseNotationError> {
+        parse_little_golem(size, string)
+    }
+
+    /// Return the moves of this game in Little Golem notation (see `from_little_golem`).
+    /// The setup of a loaded game cannot be represented and is ignored.
+    pub fn to_little_golem(&self) -> String {
+        to_little_golem(self)
+    }
+
+    /// Return input planes for neural networks (e.g. policy/value networks) describing the current position.
+    ///
+    /// The planes are, in this order:
+    ///
+    /// 0. stones of the player to move,
+    /// 1. stones of the opponent,
+    /// 2. empty cells,
+    /// 3. all ones if Black is to move, all zeros otherwise,
+    /// 4. (optional) middle cells of bridges of the player to move that are attacked by the last move (see `Board::find_attacked_bridges`),
+    /// 5. (optional) middle cells of the opponent's bridges that are attacked by the second-to-last move and have not been repaired.
+    ///
+    /// Positions are normalized so that the player to move always connects top and bottom: If White is to move,
+    /// the board is transposed and colors are swapped (see `Board::transpose_swap_colors`). `Features::symmetry` tells whether this has happened.
+    /// For finished games, the perspective of the player who would move next is used.
+    /// ```
+    /// # use hexgame::{Coords, FeatureOptions, Game};
+    /// let mut game = Game::new(11);
+    /// game.play(Coords::new(5, 5)).unwrap();
+    ///
+    /// let features = game.to_features(&FeatureOptions::default());
+    /// assert_eq!(features.data.len(), 4 * 13 * 13); // 4 planes, padding of 1 on each side
+    /// ```
+    pub fn to_features(&self, options: &FeatureOptions) -> Features {
+        extract_features(self, options)
+    }
+
+    /// Return the game-theoretic value of the current position and all winning moves of the current player,
+    /// see `Board::solve`. Returns None if the game has ended or the board is too large.
+    ///
+    /// On 4x4 boards, this also returns None until a table has been installed with `SolutionTable::install`,
+    /// because generating it takes a few seconds in release mode and more than a minute in debug mode.
+    pub fn solve(&self) -> Option<Solution> {
+        self.board.solve(self.get_current_player()?)
+    }
+
+    /// Draw the current position of this game as an SVG diagram.
+    /// With `SvgOptions::move_numbers`, stones are numbered in the order they were played (a swapped stone gets the number of the swap move).
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        game_to_svg(self, options)
+    }
+
+    /// Draw one SVG diagram for each position of this game: The initial position, the position after the first move, etc.
+    pub fn to_svg_frames(&self, options: &SvgOptions) -> Vec<String> {
+        game_to_svg_frames(self, options)
+    }
+
+    /// Draw a single animated SVG that shows the positions of `to_svg_frames` one after the other.
+    ///
+    /// ```
+    /// # use hexgame::{Coords, Game, SvgOptions};
+    /// # use std::time::Duration;
+    /// let mut game = Game::new(5);
+    /// game.play(Coords::new(2, 2)).unwrap();
+    /// game.play(Coords::new(1, 3)).unwrap();
+    /// let options = SvgOptions { move_numbers: true, ..SvgOptions::default() };
+    /// let svg = game.to_animated_svg(&options, Duration::from_secs(1));
+    /// # assert!(svg.contains("<set"));
+    /// ```
+    pub fn to_animated_svg(&self, options: &SvgOptions, frame_duration: Duration) -> String {
+        game_to_animated_svg(self, options, frame_duration)
     }
 
     /// Let the current player place a stone at the given coordinates.
@@ -73,9 +359,10 @@ impl Game {
         match self.status {
             Status::Ongoing(current_player) => {
                 self.board.play(coords, current_player)?;
+                self.moves.push(Move::Place(coords));
 
                 if Self::is_finished_after_player(&self.board, current_player) {
-                    self.status = Status::Finished(current_player);
+                    self.finish(GameResult::new(current_player, WinReason::Connection));
                 } else {
                     self.status = Status::Ongoing(current_player.opponent_color());
                 }
@@ -86,6 +373,75 @@ impl Game {
         }
     }
 
+    /// Let the second player swap instead of placing a stone.
+    ///
+    /// This is only allowed as White's first move, if the swap rule is enabled in the game's metadata
+    /// and the game was started on an empty board.
+    /// Black's stone is replaced by a white stone mirrored at the long diagonal (see `Board::transpose_swap_colors`) and Black moves next.
+    /// This is equivalent to exchanging the players' colors, but keeps the convention that Black connects top and bottom.
+    pub fn swap(&mut self) -> Result<(), InvalidMove> {
+        match (self.status, self.moves.as_slice()) {
+            (Status::Finished(_), _) => return Err(InvalidMove::GameOver),
+            (Status::Ongoing(Color::White), [Move::Place(_)])
+                if self.setup.is_none() && self.get_metadata().swap_rule => {}
+            _ => return Err(InvalidMove::SwapNotAllowed),
+        }
+
+        // The board contains only the first stone, so this is the same as placing a white stone at the mirrored position.
+        self.board = self.board.transpose_swap_colors();
+        self.status = Status::Ongoing(Color::Black);
+        self.moves.push(Move::Swap);
+
+        Ok(())
+    }
+
+    /// Play the given move, i.e. either call `play` or `swap`.
+    pub fn play_move(&mut self, mv: Move) -> Result<(), InvalidMove> {
+        match mv {
+            Move::Place(coords) => self.play(coords),
+            Move::Swap => self.swap(),
+        }
+    }
+
+    /// Let the given player resign. The opponent wins the game.
+    ///
+    /// Players may resign at any time, not only when it is their turn.
+    /// If the game has already ended, this method returns an error.
+    pub fn resign(&mut self, color: Color) -> Result<(), InvalidMove> {
+        self.end(color, WinReason::Resignation)
+    }
+
+    /// Let the given player forfeit the game, e.g. because they have attempted illegal moves.
+    /// The opponent wins the game.
+    ///
+    /// If the game has already ended, this method returns an error.
+    pub fn forfeit(&mut self, color: Color) -> Result<(), InvalidMove> {
+        self.end(color, WinReason::Forfeit)
+    }
+
+    /// Let the given player lose because they have exceeded their time. The opponent wins the game.
+    ///
+    /// Usually, this method is called by `TimedGame`. If the game has already ended, this method returns an error.
+    pub fn lose_on_time(&mut self, color: Color) -> Result<(), InvalidMove> {
+        self.end(color, WinReason::Timeout)
+    }
+
+    fn end(&mut self, loser: Color, reason: WinReason) -> Result<(), InvalidMove> {
+        match self.status {
+            Status::Ongoing(_) => {
+                self.finish(GameResult::new(loser.opponent_color(), reason));
+                Ok(())
+            }
+            Status::Finished(_) => Err(InvalidMove::GameOver),
+        }
+    }
+
+    /// Mark the game as finished with the given result, regardless of the current state.
+    pub(crate) fn finish(&mut self, result: GameResult) {
+        self.status = Status::Finished(result.winner);
+        self.result = Some(result);
+    }
+
     fn compute_status(
         board: &mut Board,
         current_player: Option<Color>,
@@ -112,6 +468,13 @@ impl Game {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_game_is_send() {
+        // Required to search positions in several threads (see `MctsOptions::threads`)
+        fn assert_send<T: Send>() {}
+        assert_send::<Game>();
+    }
+
     #[test]
     fn test_match() {
         let mut game = Game::new(3);
@@ -148,6 +511,10 @@ mod test {
         let _ = game.play(Coords { row: 2, column: 1 }); // black wins here
 
         assert_eq!(game.status, Status::Finished(Color::Black));
+        assert_eq!(
+            game.get_result(),
+            Some(GameResult::new(Color::Black, WinReason::Connection))
+        );
     }
 
     #[test]
@@ -198,5 +565,136 @@ mod test {
 
         assert_eq!(game.status, Status::Finished(Color::White));
         assert_eq!(game.get_current_player(), None);
+        assert_eq!(
+            game.get_result(),
+            Some(GameResult::new(Color::White, WinReason::Connection))
+        );
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mut game = Game::new(3);
+        assert!(game.get_metadata().is_empty());
+
+        game.get_metadata_mut().event = Some("Test".to_string());
+        let mut clone = game.clone();
+        clone.get_metadata_mut().event = Some("Clone".to_string());
+
+        assert_eq!(game.get_metadata().event.as_deref(), Some("Test"));
+        assert_eq!(clone.get_metadata().event.as_deref(), Some("Clone"));
+
+        game.set_metadata(GameMetadata::default());
+        assert!(game.metadata.is_none());
+    }
+
+    #[test]
+    fn test_get_moves() {
+        let mut game = Game::new(3);
+        game.play(Coords::new(1, 1)).unwrap();
+        game.play(Coords::new(0, 2)).unwrap();
+        let _ = game.play(Coords::new(0, 2));
+
+        assert_eq!(
+            game.get_moves(),
+            &[
+                Move::Place(Coords::new(1, 1)),
+                Move::Place(Coords::new(0, 2))
+            ]
+        );
+        assert_eq!(game.get_setup(), None);
+    }
+
+    #[test]
+    fn test_loaded_game_has_setup() {
+        let stone_matrix = vec![vec![None, Some(Color::Black)], vec![None, None]];
+        let mut game = Game::load(stone_matrix.clone(), Some(Color::White)).unwrap();
+        game.play(Coords::new(1, 0)).unwrap();
+
+        let setup = game.get_setup().unwrap();
+        assert_eq!(setup.stones, stone_matrix);
+        assert_eq!(setup.current_player, Some(Color::White));
+        assert_eq!(game.get_moves(), &[Move::Place(Coords::new(1, 0))]);
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut game = Game::new(3);
+        game.get_metadata_mut().swap_rule = true;
+        game.play(Coords::new(0, 2)).unwrap();
+        game.swap().unwrap();
+
+        assert_eq!(game.board.get_color(Coords::new(0, 2)), None);
+        assert_eq!(game.board.get_color(Coords::new(2, 0)), Some(Color::White));
+        assert_eq!(game.status, Status::Ongoing(Color::Black));
+        assert_eq!(
+            game.get_moves(),
+            &[Move::Place(Coords::new(0, 2)), Move::Swap]
+        );
+    }
+
+    #[test]
+    fn test_swap_requires_swap_rule() {
+        let mut game = Game::new(3);
+        game.play(Coords::new(0, 2)).unwrap();
+        assert_eq!(game.swap(), Err(InvalidMove::SwapNotAllowed));
+    }
+
+    #[test]
+    fn test_swap_only_as_second_move() {
+        let mut game = Game::new(3);
+        game.get_metadata_mut().swap_rule = true;
+        assert_eq!(game.swap(), Err(InvalidMove::SwapNotAllowed));
+
+        game.play(Coords::new(0, 2)).unwrap();
+        game.play(Coords::new(1, 1)).unwrap();
+        assert_eq!(game.play_move(Move::Swap), Err(InvalidMove::SwapNotAllowed));
+    }
+
+    #[test]
+    fn test_resign() {
+        let mut game = Game::new(3);
+        game.play(Coords::new(1, 1)).unwrap();
+        game.resign(Color::Black).unwrap();
+
+        assert_eq!(game.status, Status::Finished(Color::White));
+        assert_eq!(
+            game.get_result(),
+            Some(GameResult::new(Color::White, WinReason::Resignation))
+        );
+        assert_eq!(game.play(Coords::new(0, 0)), Err(InvalidMove::GameOver));
+    }
+
+    #[test]
+    fn test_cannot_resign_after_game_has_finished() {
+        let mut game = Game::new(3);
+        game.forfeit(Color::White).unwrap();
+
+        assert_eq!(game.resign(Color::Black), Err(InvalidMove::GameOver));
+        assert_eq!(
+            game.get_result(),
+            Some(GameResult::new(Color::Black, WinReason::Forfeit))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let status = Status::Ongoing(Color::White);
+        assert_eq!(
+            serde_json::to_string(&status).unwrap(),
+            r#"{"ongoing":"white"}"#
+        );
+        let result = GameResult::new(Color::Black, WinReason::Timeout);
+        let string = serde_json::to_string(&result).unwrap();
+        assert_eq!(string, r#"{"winner":"black","reason":"timeout"}"#);
+        assert_eq!(serde_json::from_str::<GameResult>(&string).unwrap(), result);
+    }
+
+    #[test]
+    fn test_display_result() {
+        let result = GameResult::new(Color::White, WinReason::Resignation);
+        assert_eq!(result.to_string(), "White wins by resignation");
+        let result = GameResult::new(Color::Black, WinReason::Timeout);
+        assert_eq!(result.to_string(), "Black wins on time");
     }
 }
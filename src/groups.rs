@@ -0,0 +1,224 @@
+use crate::color::Color;
+use crate::coords::Coords;
+use crate::edges::{CoordsOrEdge, Edge};
+use crate::hex_cells::{HexCells, Index};
+use crate::neighbors::get_neighbors;
+
+/// A group (or chain) is a maximal set of connected stones of the same color.
+///
+/// ```text
+///  a  b  c
+/// 1\●  .  .\1
+///  2\●  ○  .\2
+///   3\.  ○  .\3
+///      a  b  c
+/// ```
+/// On this board, Black has one group {a1, a2} with the liberties b1 and a3 which touches the left and the top edge.
+/// White has one group {b2, b3} with the liberties b1, c1, c2, a3 and c3 which touches the bottom edge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Group {
+    pub color: Color,
+    /// The stones of this group in row-major order.
+    pub stones: Vec<Coords>,
+    /// The empty cells adjacent to this group in row-major order.
+    pub liberties: Vec<Coords>,
+    /// The edges adjacent to this group (of both colors) in the order left, top, right, bottom.
+    pub edges: Vec<Edge>,
+}
+
+impl Group {
+    /// Check whether this group touches the given edge.
+    pub fn touches(&self, edge: Edge) -> bool {
+        self.edges.contains(&edge)
+    }
+}
+
+/// Return all groups of the given color, ordered by their first stone.
+pub fn groups(cells: &HexCells, color: Color) -> Vec<Group> {
+    let mut in_group = vec![false; cell_count(cells) as usize];
+    let mut result = vec![];
+
+    for index in 0..cell_count(cells) {
+        if !in_group[index as usize] && cells.get_color_at_index(index) == Some(color) {
+            result.push(collect_group(cells, index, color, &mut in_group));
+        }
+    }
+    result
+}
+
+/// Return the group containing the stone at the given coordinates or None if the cell is empty.
+pub fn group_of(cells: &HexCells, coords: Coords) -> Option<Group> {
+    let start = cells.index_from_coords(coords);
+    let color = cells.get_color_at_index(start)?;
+    let mut in_group = vec![false; cell_count(cells) as usize];
+    Some(collect_group(cells, start, color, &mut in_group))
+}
+
+/// Collect the group of the stone at `start` by a flood fill over adjacent stones. Edges are skipped because the
+/// union-find structure in `HexCells` also joins separate groups touching the same edge of their color.
+fn collect_group(cells: &HexCells, start: Index, color: Color, in_group: &mut [bool]) -> Group {
+    let mut stones = vec![start];
+    let mut stack = vec![start];
+    in_group[start as usize] = true;
+    while let Some(index) = stack.pop() {
+        for neighbor in get_neighbors(cells, index) {
+            if neighbor < cell_count(cells)
+                && !in_group[neighbor as usize]
+                && cells.get_color_at_index(neighbor) == Some(color)
+            {
+                in_group[neighbor as usize] = true;
+                stones.push(neighbor);
+                stack.push(neighbor);
+            }
+        }
+    }
+    stones.sort_unstable();
+
+    let mut group = Group {
+        color,
+        stones: stones
+            .into_iter()
+            .map(|index| cells.coords_from_index(index))
+            .collect(),
+        liberties: vec![],
+        edges: vec![],
+    };
+    add_neighbors(cells, &mut group);
+    group
+}
+
+fn cell_count(cells: &HexCells) -> Index {
+    cells.size as Index * cells.size as Index
+}
+
+/// Fill the liberties and edges of a group whose stones are known.
+fn add_neighbors(cells: &HexCells, group: &mut Group) {
+    let mut is_liberty = vec![false; cell_count(cells) as usize];
+    let mut edges = vec![];
+
+    for stone in group.stones.iter() {
+        for neighbor in get_neighbors(cells, cells.index_from_coords(*stone)) {
+            match cells.decode_index(neighbor) {
+                CoordsOrEdge::Coords(_) => {
+                    if cells.get_color_at_index(neighbor).is_none() {
+                        is_liberty[neighbor as usize] = true;
+                    }
+                }
+                CoordsOrEdge::Edge(edge) => {
+                    if !edges.contains(&edge) {
+                        edges.push(edge);
+                    }
+                }
+            }
+        }
+    }
+
+    group.liberties = (0..cell_count(cells))
+        .filter(|index| is_liberty[*index as usize])
+        .map(|index| cells.coords_from_index(index))
+        .collect();
+    group.edges = [Edge::Left, Edge::Top, Edge::Right, Edge::Bottom]
+        .into_iter()
+        .filter(|edge| edges.contains(edge))
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+
+    fn coords(names: &[&str]) -> Vec<Coords> {
+        names.iter().map(|name| name.parse().unwrap()).collect()
+    }
+
+    fn board() -> Board {
+        "
+            ●  .  .
+            ●  ○  .
+            .  ○  ●
+        "
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_groups() {
+        let board = board();
+
+        assert_eq!(
+            board.groups(Color::Black),
+            vec![
+                Group {
+                    color: Color::Black,
+                    stones: coords(&["a1", "a2"]),
+                    liberties: coords(&["b1", "a3"]),
+                    edges: vec![Edge::Left, Edge::Top],
+                },
+                Group {
+                    color: Color::Black,
+                    stones: coords(&["c3"]),
+                    liberties: coords(&["c2"]),
+                    edges: vec![Edge::Right, Edge::Bottom],
+                },
+            ]
+        );
+        assert_eq!(
+            board.groups(Color::White),
+            vec![Group {
+                color: Color::White,
+                stones: coords(&["b2", "b3"]),
+                liberties: coords(&["b1", "c1", "c2", "a3"]),
+                edges: vec![Edge::Bottom],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_groups_on_empty_board() {
+        assert!(Board::new(4).groups(Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_group_of() {
+        let board = board();
+        let groups = board.groups(Color::White);
+
+        assert_eq!(board.group_of(Coords::new(2, 1)).as_ref(), groups.first());
+        assert_eq!(board.group_of(Coords::new(0, 2)), None);
+    }
+
+    #[test]
+    fn test_winning_group_touches_both_edges() {
+        let board: Board = "
+            .  ●  .
+            ●  .  .
+            ●  .  .
+        "
+        .parse()
+        .unwrap();
+
+        let group = board.group_of(Coords::new(2, 0)).unwrap();
+        assert_eq!(group.stones, coords(&["b1", "a2", "a3"]));
+        assert!(group.touches(Edge::Top) && group.touches(Edge::Bottom));
+    }
+
+    #[test]
+    fn test_separate_groups_on_same_edge() {
+        let board: Board = "
+            ●  .  ●
+            .  .  .
+            .  .  .
+        "
+        .parse()
+        .unwrap();
+
+        let groups = board.groups(Color::Black);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].stones, coords(&["a1"]));
+        assert_eq!(groups[0].edges, vec![Edge::Left, Edge::Top]);
+        assert_eq!(groups[1].stones, coords(&["c1"]));
+        assert_eq!(groups[1].edges, vec![Edge::Top, Edge::Right]);
+        assert_eq!(board.group_of(Coords::new(0, 2)).as_ref(), groups.get(1));
+    }
+}
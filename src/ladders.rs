@@ -0,0 +1,380 @@
+use crate::board::Board;
+use crate::color::Color;
+use crate::coords::Coords;
+use crate::edges::{get_edges_of_color, Edge};
+
+/// The rows (counted from the edge) in which ladders are detected.
+const LADDER_ROWS: [u8; 2] = [2, 3];
+
+/// A ladder: The attacker has a line of stones in the second or third row from one of their edges, the defender
+/// blocks all of them in the row in front. The attacker can push along the row and threaten to connect with every
+/// move, but the defender can block each threat, until the ladder reaches the corner or an escape.
+///
+/// ```text
+///  a  b  c  d  e
+/// 1\.  .  .  .  .\1
+///  2\.  .  .  .  .\2
+///   3\.  .  .  .  .\3
+///    4\.  ●  ●  .  .\4
+///     5\○  ○  ○  .  ●\5
+///        a  b  c  d  e
+/// ```
+/// On this board, Black has a second-row ladder along the bottom edge running to the right. Its head is c4 and the
+/// stone e5 is an escape: After d4 (White d5), e4 connects Black to the bottom edge.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ladder {
+    /// The attacking player.
+    pub color: Color,
+    /// The edge the attacker is trying to reach.
+    pub edge: Edge,
+    /// The row of the ladder counted from `edge`, i.e. 2 or 3.
+    pub row: u8,
+    /// The edge the ladder is running towards.
+    pub direction: Edge,
+    /// The stone at the front end of the ladder.
+    pub head: Coords,
+    /// A stone of the attacker ahead of the ladder that the ladder will reach before running into a stone of the
+    /// defender and that is connected to the edge or not blocked by the defender. If there is an escape, the ladder
+    /// succeeds.
+    pub escape: Option<Coords>,
+}
+
+impl Ladder {
+    /// Check whether the attacker will connect to the edge by following this ladder.
+    pub fn succeeds(&self) -> bool {
+        self.escape.is_some()
+    }
+}
+
+/// Cells are addressed relative to an edge: `along` is the position parallel to the edge and `distance` counts the
+/// rows from the edge (the row touching the edge has distance 0).
+fn cell(board: &Board, edge: Edge, along: i16, distance: i16) -> Option<Coords> {
+    let size = board.size() as i16;
+    if !(0..size).contains(&along) || !(0..size).contains(&distance) {
+        return None;
+    }
+    let (row, column) = match edge {
+        Edge::Top => (distance, along),
+        Edge::Bottom => (size - 1 - distance, along),
+        Edge::Left => (along, distance),
+        Edge::Right => (along, size - 1 - distance),
+    };
+    Some(Coords::new(row as u8, column as u8))
+}
+
+/// The cell at (along, distance) touches the cells (along, distance - 1) and (along + shift, distance - 1).
+fn shift(edge: Edge) -> i16 {
+    match edge {
+        Edge::Top | Edge::Left => 1,
+        Edge::Bottom | Edge::Right => -1,
+    }
+}
+
+/// The edge a ladder runs towards if it moves in the given direction along `edge`.
+fn direction_edge(edge: Edge, direction: i16) -> Edge {
+    match (edge, direction > 0) {
+        (Edge::Top | Edge::Bottom, true) => Edge::Right,
+        (Edge::Top | Edge::Bottom, false) => Edge::Left,
+        (Edge::Left | Edge::Right, true) => Edge::Bottom,
+        (Edge::Left | Edge::Right, false) => Edge::Top,
+    }
+}
+
+pub fn ladders(board: &Board, color: Color) -> Vec<Ladder> {
+    let mut result = vec![];
+    for edge in get_edges_of_color(color) {
+        for row in LADDER_ROWS {
+            find_ladders(board, color, edge, row, &mut result);
+        }
+    }
+    result
+}
+
+fn find_ladders(board: &Board, color: Color, edge: Edge, row: u8, result: &mut Vec<Ladder>) {
+    let size = board.size() as i16;
+    let distance = row as i16 - 1;
+    if distance >= size {
+        return;
+    }
+
+    // A stone is blocked if both cells in front of it belong to the defender.
+    let is_blocked = |along: i16| {
+        let stone = cell(board, edge, along, distance).unwrap();
+        board.get_color(stone) == Some(color)
+            && !board.is_in_same_set(stone, edge)
+            && [along, along + shift(edge)].iter().all(|front| {
+                cell(board, edge, *front, distance - 1)
+                    .is_none_or(|front| board.get_color(front) == Some(color.opponent_color()))
+            })
+    };
+
+    let mut along = 0;
+    while along < size {
+        if !is_blocked(along) {
+            along += 1;
+            continue;
+        }
+        let start = along;
+        while along + 1 < size && is_blocked(along + 1) {
+            along += 1;
+        }
+        for (head, direction) in [(start, -1), (along, 1)] {
+            if let Some(ladder) = follow_ladder(board, color, edge, row, head, direction) {
+                result.push(ladder);
+            }
+        }
+        along += 1;
+    }
+}
+
+/// Check whether the attacker can push from the blocked stone at `head` in the given direction and look for an escape.
+fn follow_ladder(
+    board: &Board,
+    color: Color,
+    edge: Edge,
+    row: u8,
+    head: i16,
+    direction: i16,
+) -> Option<Ladder> {
+    let distance = row as i16 - 1;
+    // The cell at the given distance that is new in front of the ladder when it reaches `along`
+    let ahead = |along: i16, distance_from_ladder: i16| {
+        let along = if direction == shift(edge) {
+            along + distance_from_ladder * shift(edge)
+        } else {
+            along
+        };
+        cell(board, edge, along, distance - distance_from_ladder)
+    };
+
+    // The next push must threaten to connect.
+    let next = ahead(head + direction, 0)?;
+    let threat = ahead(head + direction, 1)?;
+    if board.get_color(next).is_some() || board.get_color(threat).is_some() {
+        return None;
+    }
+
+    // A stone ahead is an escape if one of its cells in front is still open once the defender has blocked the push
+    // before it. Otherwise the ladder just absorbs the stone and continues.
+    let is_escape = |stone: Coords, along: i16, distance_from_ladder: i16| {
+        board.is_in_same_set(stone, edge)
+            || [along, along - direction].into_iter().any(|front_along| {
+                let is_next_block = distance_from_ladder == 0 && front_along == along - direction;
+                ahead(front_along, distance_from_ladder + 1).is_some_and(|front| {
+                    !is_next_block && board.get_color(front) != Some(color.opponent_color())
+                })
+            })
+    };
+
+    let mut escape = None;
+    let mut along = head + direction;
+    'search: while cell(board, edge, along, distance).is_some() {
+        for distance_from_ladder in 0..=distance {
+            let coords = match ahead(along, distance_from_ladder) {
+                Some(coords) => coords,
+                None => continue,
+            };
+            match board.get_color(coords) {
+                Some(stone) if stone == color && is_escape(coords, along, distance_from_ladder) => {
+                    escape = Some(coords);
+                    break 'search;
+                }
+                Some(stone) if stone == color => {}
+                Some(_) => break 'search,
+                None => {}
+            }
+        }
+        along += direction;
+    }
+
+    Some(Ladder {
+        color,
+        edge,
+        row,
+        direction: direction_edge(edge, direction),
+        head: cell(board, edge, head, distance).unwrap(),
+        escape,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coords(s: &str) -> Coords {
+        s.parse().unwrap()
+    }
+
+    fn second_row_ladder(escape: Option<&str>) -> Ladder {
+        Ladder {
+            color: Color::Black,
+            edge: Edge::Bottom,
+            row: 2,
+            direction: Edge::Right,
+            head: coords("c4"),
+            escape: escape.map(coords),
+        }
+    }
+
+    #[test]
+    fn test_second_row_ladder() {
+        let board: Board = "
+            .  .  .  .  .
+            .  .  .  .  .
+            .  .  .  .  .
+            .  ●  ●  .  .
+            ○  ○  ○  .  .
+        "
+        .parse()
+        .unwrap();
+
+        // The ladder cannot run to the left, because a4 does not threaten to connect.
+        assert_eq!(board.ladders(Color::Black), vec![second_row_ladder(None)]);
+        assert!(!board.ladders(Color::Black)[0].succeeds());
+        assert!(board.ladders(Color::White).is_empty());
+    }
+
+    #[test]
+    fn test_escape() {
+        let board: Board = "
+            .  .  .  .  .
+            .  .  .  .  .
+            .  .  .  .  .
+            .  ●  ●  .  .
+            ○  ○  ○  .  ●
+        "
+        .parse()
+        .unwrap();
+
+        let ladders = board.ladders(Color::Black);
+        assert_eq!(ladders, vec![second_row_ladder(Some("e5"))]);
+        assert!(ladders[0].succeeds());
+    }
+
+    #[test]
+    fn test_defender_stone_ahead() {
+        let board: Board = "
+            .  .  .  .  .
+            .  .  .  .  .
+            .  .  .  .  .
+            .  ●  ●  .  ○
+            ○  ○  ○  .  ●
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(board.ladders(Color::Black), vec![second_row_ladder(None)]);
+    }
+
+    #[test]
+    fn test_blocked_stone_ahead() {
+        // After d6 (White d7), e6 is blocked as well and the ladder runs into the corner.
+        let board: Board = "
+            .  .  .  .  .  .  .
+            .  .  .  .  .  .  .
+            .  .  .  .  .  .  .
+            .  .  .  .  .  .  .
+            .  .  .  .  .  .  .
+            .  ●  ●  .  ●  .  .
+            ○  ○  ○  .  ○  .  .
+        "
+        .parse()
+        .unwrap();
+
+        let ladders = board.ladders(Color::Black);
+        assert_eq!(
+            ladders,
+            vec![Ladder {
+                head: coords("c6"),
+                ..second_row_ladder(None)
+            }]
+        );
+        assert!(!ladders[0].succeeds());
+    }
+
+    #[test]
+    fn test_unblocked_stone_ahead() {
+        // After d6 (White d7), e6 connects via e7.
+        let board: Board = "
+            .  .  .  .  .  .  .
+            .  .  .  .  .  .  .
+            .  .  .  .  .  .  .
+            .  .  .  .  .  .  .
+            .  .  .  .  .  .  .
+            .  ●  ●  .  ●  .  .
+            ○  ○  ○  .  .  .  .
+        "
+        .parse()
+        .unwrap();
+
+        let ladders = board.ladders(Color::Black);
+        assert_eq!(
+            ladders,
+            vec![Ladder {
+                head: coords("c6"),
+                ..second_row_ladder(Some("e6"))
+            }]
+        );
+        assert!(ladders[0].succeeds());
+    }
+
+    #[test]
+    fn test_third_row_ladder_in_both_directions() {
+        let board: Board = "
+            .  .  .  .  .  .
+            .  ●  ○  .  .  .
+            .  ●  ○  .  .  .
+            .  ●  .  .  .  .
+            .  .  .  .  .  .
+            ○  .  .  .  .  .
+        "
+        .parse()
+        .unwrap();
+
+        let ladder = Ladder {
+            color: Color::White,
+            edge: Edge::Left,
+            row: 3,
+            direction: Edge::Top,
+            head: coords("c2"),
+            escape: None,
+        };
+        assert_eq!(
+            board.ladders(Color::White),
+            vec![
+                ladder,
+                Ladder {
+                    direction: Edge::Bottom,
+                    head: coords("c3"),
+                    escape: Some(coords("a6")),
+                    ..ladder
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_ladders() {
+        // The cells in front of a2 and b2 are empty.
+        let board: Board = "
+            .  .  .  .
+            ●  ●  .  .
+            .  .  .  .
+            .  .  .  .
+        "
+        .parse()
+        .unwrap();
+        assert!(board.ladders(Color::Black).is_empty());
+
+        // b3 is blocked, but connected to the bottom edge via c3 and c4.
+        let board: Board = "
+            .  .  .  .
+            .  .  .  .
+            .  ●  ●  .
+            ○  ○  ●  .
+        "
+        .parse()
+        .unwrap();
+        assert!(board.ladders(Color::Black).is_empty());
+    }
+}
@@ -5,9 +5,12 @@ While it was mainly written to be used in Monte-Carlo Tree Search bots for Hex,
 
 Features:
 
-* rules of the game (without swap rule, see below),
-* serialize/deserialize to/from JSON,
-* some analysis functions that may be helpful when writing bots: `get_neighbors`, `get_empty_cells`, `find_attacked_bridges`. See the `Board` struct for more information.
+* rules of the game (including the swap rule, see below),
+* serialize/deserialize to/from JSON and a compact binary format,
+* some analysis functions that may be helpful when writing bots: `get_neighbors`, `get_empty_cells`, `find_attacked_bridges`, `bridges`, `broken_bridges`, `groups`, `winning_moves`, `threats`, `ladders`. See the `Board` struct for more information.
+* board symmetries (`rotate_180`, `transpose_swap_colors`) and canonical forms of positions for opening books and transposition tables, see `Symmetry`.
+* input planes for neural networks, see `Game::to_features`.
+* a Monte-Carlo tree search player (`Mcts`) and a self-play generator for training data (`SelfPlay`).
 
 # The Game of Hex
 
@@ -32,7 +35,8 @@ Hex was invented in the 1940s by Piet Hein and, independently, by John Nash. Ple
 
 As starting player, Black has a huge advantage. In real games, this advantage is circumvented by the so-called swap rule: After Black has placed the first Stone, the second player can choose to either continue the game normally or to swap colors.
 
-This library does not yet implement the swap rule, mostly because it was not necessary for the MCTS-research using this library.
+The swap rule is disabled by default. To enable it, set `swap_rule` in the game's metadata. White may then call `game.swap()` instead of placing their first stone.
+This library implements the swap rule by mirroring Black's first stone at the long diagonal and changing its color to White. This way, Black always connects top and bottom.
 
 # How to use this library
 
@@ -49,13 +53,61 @@ game.play("d5".parse().unwrap());
 ```
 
 `game.board` can be used to access the cells of the board (e.g. `get_color(coords)`).
+Boards can be printed with `Display` as shown above. `board.render(&options)` supports further options (see `RenderOptions`),
+e.g. ASCII symbols, ANSI colors, highlighted cells, zero-based coordinates and a flat layout.
+For publications, `board.to_svg(&options)` and `game.to_svg(&options)` draw SVG diagrams with optional move numbers, markers and arrows (see `SvgOptions`).
+`game.to_svg_frames` and `game.to_animated_svg` show the course of a game.
+
+Games usually end when one player connects their edges, but players may also `resign`.
+Once the game has ended, `game.get_result()` tells who has won and why (e.g. "White wins by resignation").
+
+To play with clocks, wrap the game into a `TimedGame` with one of the time controls in `TimeControl` (absolute, Fischer increment or byo-yomi).
 
 ## Serialization
 
-Serialization functionality requires `use hexgame::Serialization;`.
+Serialization functionality requires the `serde` feature (enabled by default) and `use hexgame::Serialization;`.
 
 To serialize a game, use `game.save_to_string()` or `game.save_to_json()`, which serializes to a [Serde](https://serde.rs/) value.
 `Game::load_from_str` or `Game::load_from_json` can be used to create a game from a JSON string or value.
+If the data is invalid, these methods return a `LoadError` describing the problem.
+The JSON format stores the list of moves and metadata attached to a game (player names, event, date, etc., see `GameMetadata`).
+The `serde` feature also implements Serde's `Serialize` and `Deserialize` for the public types, e.g. `Coords` (as "c4" strings), `Color`, `Move`, `Status` and `Board`.
+Without the `serde` feature, this crate does not depend on serde or serde_json at all.
+
+Files written in the older version 1 format (only the current position) can still be loaded. To produce such files, use `game.save_to_json_with_version(FormatVersion::V1)`.
+
+For large datasets, `game.to_bytes()` and `Game::from_bytes` provide a compact binary encoding of positions (2 bits per cell).
+`PositionWriter` and `PositionReader` write and read streams of many positions.
+
+Games can also be exchanged in the notations of popular Hex sites: `Game::from_trmph`/`game.to_trmph()` for trmph URLs
+(e.g. "https://trmph.com/hex/board#11,a1b2c3") and `Game::from_little_golem`/`game.to_little_golem()` for Little Golem move strings (e.g. "cd swap ef").
+These notations do not require the `serde` feature.
+
+To analyze games with variations, comments and labels, use `GameTree`. It can be loaded from and saved to the SGF dialect of HexGui,
+and each node can be replayed into a `Game`.
+
+## Computer players
+
+`Mcts` is a simple computer player based on Monte-Carlo tree search with random playouts and optional RAVE (see `MctsOptions`).
+It implements the `Player` trait; `mcts.analyze(&game)` returns the visit count and value of each searched move.
+Set `threads` in the options to search with several threads.
+`Board` is `Send`, but not `Sync`. To share a position between threads read-only, use `board.freeze()`, which returns a `FrozenBoard`.
+
+For AlphaZero-style experiments, `Puct` replaces the random playouts by a model implementing `Evaluator`, which returns
+move priors and a value for a position. Leaves are evaluated in batches (see `PuctOptions`), so that models can process
+many positions at once. `UniformEvaluator` and the tiny `DenseNetwork` serve as reference implementations.
+
+`SelfPlay` lets `Mcts` play against itself to generate training data for neural networks. Each `TrainingSample` contains
+a position, the visit counts of the search and the outcome of the game. Samples can be augmented with all symmetric variants
+of the position. `SampleWriter` and `SampleReader` write and read them in a compact binary format.
+All randomness is derived from the seed in the options, so runs are reproducible.
+
+An `OpeningBook` maps positions to recommended moves with weights. Positions are stored in a canonical form, so
+symmetric positions share their moves. Books can be built from game records, stored in a simple text format and
+consulted by `BookPlayer` (which wraps another `Player`) and by `SelfPlay`.
+
+Boards up to 4x4 can be solved completely: `board.solve(player)` returns the winner with perfect play and all winning moves,
+which is useful as ground truth for testing bots. The underlying `SolutionTable` can also be generated and stored explicitly.
 
 # Playing Hex on CLI
 
@@ -64,26 +116,90 @@ While this package is mostly a library, it also contains a small command-line in
 cargo run
 ```
 Then type the coordinates of the space where you would like to place your next stone, e.g. "c2" and press Enter.
+Type "resign" to give up the game.
 
 Optionally, you can specify the size of the board like in `cargo run 7`.
+A time control may be given as second argument, e.g. `cargo run 9 300+5` for 5 minutes per player and an increment of 5 seconds per move.
+See `TimeControl` for the supported formats.
+
+To generate self-play training data, run
+```text
+cargo run --release selfplay <size> <games> <iterations> <output file> [seed] [--augment] [--book <file>]
+```
+With `--book`, positions from the given opening book (see `OpeningBook`) are played without searching.
+
+`cargo run --release solve <size> <output file>` writes the `SolutionTable` of the given size (up to 4) to a file.
+Load it with `SolutionTable::from_bytes` and call `install` before solving positions of that size (required for 4x4,
+since that table is not generated on demand).
 */
 mod attacked_bridges;
+mod binary;
 mod board;
+mod book;
+mod bridges;
+mod clock;
 mod color;
 mod coords;
 mod edges;
 mod errors;
+mod evaluator;
+mod features;
 mod format;
+mod frozen_board;
 mod game;
+mod groups;
 mod hex_cells;
+mod ladders;
+mod mcts;
+mod metadata;
+mod moves;
 mod neighbors;
+mod network;
+mod notation;
+mod player;
+mod puct;
+mod random;
+mod selfplay;
+#[cfg(feature = "serde")]
 mod serialize;
+mod sgf;
+mod solver;
+mod svg;
+mod symmetry;
+mod threats;
 mod union_find;
 
+pub use crate::binary::{PositionReader, PositionWriter};
 pub use crate::board::{Board, StoneMatrix, MAX_BOARD_SIZE, MIN_BOARD_SIZE};
+pub use crate::book::{BookMove, BookPlayer, OpeningBook};
+pub use crate::bridges::Bridge;
+pub use crate::clock::{
+    Clock, ManualTimeSource, ParseTimeControlError, SystemTimeSource, TimeControl, TimeSource,
+    TimedGame, Timeout,
+};
 pub use crate::color::Color;
-pub use crate::coords::{CoordValue, Coords};
+pub use crate::coords::{CoordValue, Coords, ParseCoordsError};
 pub use crate::edges::{CoordsOrEdge, Edge};
-pub use crate::errors::{InvalidBoard, InvalidMove};
-pub use crate::game::{Game, Status};
-pub use crate::serialize::Serialization;
+pub use crate::errors::{
+    InvalidBoard, InvalidMove, LoadError, ParseBookError, ParseNotationError, ParseSgfError,
+};
+pub use crate::evaluator::{Evaluation, Evaluator, UniformEvaluator};
+pub use crate::features::{FeatureOptions, Features};
+pub use crate::format::{Layout, RenderOptions};
+pub use crate::frozen_board::FrozenBoard;
+pub use crate::game::{Game, GameResult, Setup, Status, WinReason};
+pub use crate::groups::Group;
+pub use crate::ladders::Ladder;
+pub use crate::mcts::{Analysis, Mcts, MctsOptions, MoveAnalysis};
+pub use crate::metadata::{GameMetadata, PlayerInfo};
+pub use crate::moves::Move;
+pub use crate::network::DenseNetwork;
+pub use crate::player::Player;
+pub use crate::puct::{Puct, PuctOptions};
+pub use crate::selfplay::{SampleReader, SampleWriter, SelfPlay, SelfPlayOptions, TrainingSample};
+#[cfg(feature = "serde")]
+pub use crate::serialize::{FormatVersion, Serialization};
+pub use crate::sgf::{GameTree, Node, NodeId};
+pub use crate::solver::{Solution, SolutionTable, MAX_SOLVED_SIZE};
+pub use crate::svg::{Marker, SvgOptions};
+pub use crate::symmetry::Symmetry;
@@ -1,64 +1,208 @@
 use hexgame::{Color, CoordValue, Coords, MAX_BOARD_SIZE, MIN_BOARD_SIZE};
-use hexgame::{Game, Status};
+use hexgame::{Game, Status, SystemTimeSource, TimeControl, TimedGame};
+use hexgame::{MctsOptions, OpeningBook, SampleWriter, SelfPlay, SelfPlayOptions};
+use hexgame::{SolutionTable, MAX_SOLVED_SIZE};
 use std::env;
+use std::fs;
+use std::fs::File;
 use std::io;
 use std::io::Write;
 use std::str::FromStr;
+use std::time::Duration;
 
 const DEFAULT_SIZE: CoordValue = 9;
 
+#[derive(Debug, PartialEq)]
+struct SelfPlayArguments {
+    options: SelfPlayOptions,
+    output: String,
+    book: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Play(Coords),
+    Resign,
+}
+
 fn main() {
-    let size = match read_size() {
-        Ok(size) => size,
+    let args: Vec<String> = env::args().collect();
+    let result = match args.get(1).map(String::as_str) {
+        Some("selfplay") => Some(selfplay(&args[2..])),
+        Some("solve") => Some(solve(&args[2..])),
+        _ => None,
+    };
+    if let Some(result) = result {
+        if let Err(error) = result {
+            println!("Error: {}", error);
+        }
+        return;
+    }
+
+    let (size, time_control) = match read_arguments(&args) {
+        Ok(arguments) => arguments,
         Err(error) => {
             println!("Error: {}", error);
             return;
         }
     };
 
-    let mut game = Game::new(size);
-    println!("{}", &game.get_board());
+    let mut game = TimedGame::new(Game::new(size), time_control, SystemTimeSource::new());
+    println!("{}", &game.get_game().get_board());
 
     loop {
-        match game.get_status() {
+        match game.get_game().get_status() {
             Status::Ongoing(current_player) => {
-                let result = request_coords(&game, current_player)
-                    .and_then(|coords| play(&mut game, coords));
+                let result =
+                    request_command(&game, current_player).and_then(|command| match command {
+                        Command::Play(coords) => play(&mut game, coords),
+                        Command::Resign => resign(&mut game, current_player),
+                    });
 
                 match result {
                     Ok(_) => {
-                        println!("{}", game.get_board());
+                        println!("{}", game.get_game().get_board());
                     }
                     Err(error) => {
                         println!("Error: {}", error);
                     }
                 }
             }
-            Status::Finished(color) => {
-                println!("Game Over! The winner is {:?}", color);
+            Status::Finished(_) => {
+                if let Some(result) = game.get_game().get_result() {
+                    println!("Game Over! {}", result);
+                }
                 return;
             }
         }
     }
 }
 
-fn read_size() -> std::io::Result<CoordValue> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() > 2 {
+fn read_arguments(args: &[String]) -> std::io::Result<(CoordValue, TimeControl)> {
+    if args.len() > 3 {
         return Err(invalid_input(
-            "Expected at most one command line argument - the size of the board",
+            "Expected at most two command line arguments - the size of the board and the time control",
         ));
     }
 
-    if args.len() == 2 {
-        args[1]
+    let size = match args.get(1) {
+        Some(size) => size
             .parse::<CoordValue>()
             .map_err(|e| invalid_input(&e.to_string()))
-            .and_then(check_size)
-    } else {
-        Ok(DEFAULT_SIZE)
+            .and_then(check_size)?,
+        None => DEFAULT_SIZE,
+    };
+
+    let time_control = match args.get(2) {
+        Some(time_control) => time_control
+            .parse::<TimeControl>()
+            .map_err(|e| invalid_input(&e.to_string()))?,
+        None => TimeControl::Unlimited,
+    };
+
+    Ok((size, time_control))
+}
+
+fn selfplay(args: &[String]) -> std::io::Result<()> {
+    let arguments = read_selfplay_arguments(args)?;
+    let mut self_play = SelfPlay::new(arguments.options);
+    if let Some(path) = &arguments.book {
+        let book = fs::read_to_string(path)?
+            .parse::<OpeningBook>()
+            .map_err(|error| invalid_input(&error.to_string()))?;
+        self_play.set_book(book);
+    }
+
+    let mut writer = SampleWriter::new(io::BufWriter::new(File::create(&arguments.output)?));
+    let count = self_play.run(&mut writer)?;
+    println!(
+        "Wrote {} samples from {} games to {}",
+        count, arguments.options.games, arguments.output
+    );
+    Ok(())
+}
+
+fn read_selfplay_arguments(args: &[String]) -> std::io::Result<SelfPlayArguments> {
+    let usage = || {
+        invalid_input(
+            "Usage: hexgame selfplay <size> <games> <iterations> <output file> [seed] [--augment] [--book <file>]",
+        )
+    };
+
+    let mut augment = false;
+    let mut book = None;
+    let mut positional = vec![];
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--augment" => augment = true,
+            "--book" => book = Some(iter.next().ok_or_else(usage)?.clone()),
+            _ => positional.push(arg),
+        }
+    }
+    let args = positional;
+
+    if !(4..=5).contains(&args.len()) {
+        return Err(usage());
+    }
+
+    let size = parse_argument::<CoordValue>(args[0]).and_then(check_size)?;
+    let games = parse_argument::<u32>(args[1])?;
+    let iterations = parse_argument::<u32>(args[2])?;
+    if iterations == 0 {
+        return Err(invalid_input("The number of iterations must be positive"));
+    }
+    let seed = match args.get(4) {
+        Some(seed) => parse_argument::<u64>(seed)?,
+        None => 0,
+    };
+
+    let options = SelfPlayOptions {
+        size,
+        games,
+        mcts: MctsOptions {
+            iterations,
+            ..MctsOptions::default()
+        },
+        augment,
+        seed,
+        ..SelfPlayOptions::default()
+    };
+    Ok(SelfPlayArguments {
+        options,
+        output: args[3].clone(),
+        book,
+    })
+}
+
+fn solve(args: &[String]) -> std::io::Result<()> {
+    let (size, path) = read_solve_arguments(args)?;
+    let table = SolutionTable::generate(size);
+    fs::write(&path, table.to_bytes())?;
+    println!("Wrote solution table for {}x{} to {}", size, size, path);
+    Ok(())
+}
+
+fn read_solve_arguments(args: &[String]) -> std::io::Result<(CoordValue, String)> {
+    if args.len() != 2 {
+        return Err(invalid_input("Usage: hexgame solve <size> <output file>"));
     }
+    let size = parse_argument::<CoordValue>(&args[0])?;
+    if !(MIN_BOARD_SIZE..=MAX_SOLVED_SIZE).contains(&size) {
+        return Err(invalid_input(&format!(
+            "Size must be between {} and {}",
+            MIN_BOARD_SIZE, MAX_SOLVED_SIZE
+        )));
+    }
+    Ok((size, args[1].clone()))
+}
+
+fn parse_argument<T: FromStr>(arg: &str) -> std::io::Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    arg.parse::<T>()
+        .map_err(|e| invalid_input(&format!("Invalid argument '{}': {}", arg, e)))
 }
 
 fn check_size(size: CoordValue) -> std::io::Result<CoordValue> {
@@ -72,28 +216,54 @@ fn check_size(size: CoordValue) -> std::io::Result<CoordValue> {
     }
 }
 
-fn request_coords(game: &Game, current_player: Color) -> Result<Coords, io::Error> {
+fn request_command(
+    game: &TimedGame<SystemTimeSource>,
+    current_player: Color,
+) -> Result<Command, io::Error> {
     let player = match current_player {
         Color::Black => "BLACK",
         Color::White => "WHITE",
     };
+    let clock = game.get_clock();
+    let remaining = match clock.get_time_control() {
+        TimeControl::Unlimited => String::new(),
+        TimeControl::ByoYomi { .. } => format!(
+            " ({} + {} periods)",
+            format_duration(clock.get_remaining(current_player)),
+            clock.get_periods_left(current_player)
+        ),
+        _ => format!(
+            " ({})",
+            format_duration(clock.get_remaining(current_player))
+        ),
+    };
     print!(
-        "{}: Please enter the coordinates for your next move: ",
-        player
+        "{}{}: Please enter the coordinates for your next move (or 'resign'): ",
+        player, remaining
     );
     io::stdout().flush()?;
 
-    read_coords(&mut io::stdin().lock(), game.get_board().size())
+    read_command(&mut io::stdin().lock(), game.get_game().get_board().size())
 }
 
-fn read_coords<Reader: io::BufRead>(
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn read_command<Reader: io::BufRead>(
     reader: &mut Reader,
     board_size: CoordValue,
-) -> Result<Coords, io::Error> {
+) -> Result<Command, io::Error> {
     let mut input = String::new();
     reader.read_line(&mut input).expect("Failed to read line");
 
-    Coords::from_str(input.trim())
+    let input = input.trim();
+    if input == "resign" {
+        return Ok(Command::Resign);
+    }
+
+    Coords::from_str(input)
         .map_err(|error| invalid_input(&error.to_string()))
         .and_then(|coords| {
             if coords.is_on_board_with_size(board_size) {
@@ -106,13 +276,19 @@ fn read_coords<Reader: io::BufRead>(
                 )))
             }
         })
+        .map(Command::Play)
 }
 
-fn play(game: &mut Game, coords: Coords) -> Result<(), io::Error> {
+fn play(game: &mut TimedGame<SystemTimeSource>, coords: Coords) -> Result<(), io::Error> {
     game.play(coords)
         .map_err(|error| invalid_input(&error.to_string()))
 }
 
+fn resign(game: &mut TimedGame<SystemTimeSource>, color: Color) -> Result<(), io::Error> {
+    game.resign(color)
+        .map_err(|error| invalid_input(&error.to_string()))
+}
+
 fn invalid_input(message: &str) -> io::Error {
     io::Error::new(io::ErrorKind::InvalidInput, message)
 }
@@ -131,31 +307,103 @@ mod test {
         );
     }
 
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_read_selfplay_arguments() {
+        let args = to_args(&["5", "10", "200", "out.bin", "--augment", "7"]);
+        let arguments = read_selfplay_arguments(&args).unwrap();
+
+        let options = arguments.options;
+        assert_eq!(options.size, 5);
+        assert_eq!(options.games, 10);
+        assert_eq!(options.mcts.iterations, 200);
+        assert_eq!(options.seed, 7);
+        assert!(options.augment);
+        assert_eq!(arguments.output, "out.bin");
+        assert_eq!(arguments.book, None);
+    }
+
+    #[test]
+    fn test_read_selfplay_arguments_with_book() {
+        let args = to_args(&["5", "10", "200", "--book", "book.txt", "out.bin"]);
+        let arguments = read_selfplay_arguments(&args).unwrap();
+
+        assert_eq!(arguments.book, Some("book.txt".to_string()));
+        assert_eq!(arguments.output, "out.bin");
+        assert!(!arguments.options.augment);
+
+        let result = read_selfplay_arguments(&to_args(&["5", "10", "200", "out.bin", "--book"]));
+        assert_contains(result.unwrap_err(), "Usage");
+    }
+
+    #[test]
+    fn test_read_selfplay_arguments_with_invalid_arguments() {
+        let result = read_selfplay_arguments(&to_args(&["5", "10"]));
+        assert_contains(result.unwrap_err(), "Usage");
+
+        let result = read_selfplay_arguments(&to_args(&["5", "ten", "200", "out.bin"]));
+        assert_contains(result.unwrap_err(), "Invalid argument 'ten'");
+
+        let result = read_selfplay_arguments(&to_args(&["1", "10", "200", "out.bin"]));
+        assert_contains(result.unwrap_err(), "Size must be between");
+
+        let result = read_selfplay_arguments(&to_args(&["5", "1", "0", "out.bin"]));
+        assert_contains(result.unwrap_err(), "iterations must be positive");
+    }
+
+    #[test]
+    fn test_read_solve_arguments() {
+        let result = read_solve_arguments(&to_args(&["3", "table.bin"]));
+        assert_eq!(result.unwrap(), (3, "table.bin".to_string()));
+
+        let result = read_solve_arguments(&to_args(&["5", "table.bin"]));
+        assert_contains(result.unwrap_err(), "Size must be between 2 and 4");
+
+        let result = read_solve_arguments(&to_args(&["3"]));
+        assert_contains(result.unwrap_err(), "Usage");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(65)), "1:05");
+        assert_eq!(format_duration(Duration::from_millis(59_999)), "0:59");
+    }
+
     #[test]
     fn test_read_coords_with_valid_coords() {
         let mut input = io::BufReader::new("c2".as_bytes());
-        let result = read_coords(&mut input, 3);
-        assert_eq!(result.unwrap(), Coords { row: 1, column: 2 });
+        let result = read_command(&mut input, 3);
+        assert_eq!(result.unwrap(), Command::Play(Coords { row: 1, column: 2 }));
+    }
+
+    #[test]
+    fn test_read_command_resign() {
+        let mut input = io::BufReader::new("resign\n".as_bytes());
+        let result = read_command(&mut input, 3);
+        assert_eq!(result.unwrap(), Command::Resign);
     }
 
     #[test]
     fn test_read_coords_with_invalid_format() {
         let mut input = io::BufReader::new("b-1".as_bytes());
-        let result = read_coords(&mut input, 3);
+        let result = read_command(&mut input, 3);
         assert_contains(result.unwrap_err(), "Invalid coordinates");
     }
 
     #[test]
     fn test_read_coords_with_row_out_of_bounds() {
         let mut input = io::BufReader::new("a4".as_bytes());
-        let result = read_coords(&mut input, 3);
+        let result = read_command(&mut input, 3);
         assert_contains(result.unwrap_err(), "must be in range a1 to c3");
     }
 
     #[test]
     fn test_read_coords_with_column_out_of_bounds() {
         let mut input = io::BufReader::new("d2".as_bytes());
-        let result = read_coords(&mut input, 3);
+        let result = read_command(&mut input, 3);
         assert_contains(result.unwrap_err(), "must be in range a1 to c3");
     }
 }
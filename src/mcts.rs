@@ -0,0 +1,515 @@
+use crate::board::Board;
+use crate::color::Color;
+use crate::coords::{CoordValue, Coords};
+use crate::edges::get_edges_of_color;
+use crate::game::{Game, Status};
+use crate::moves::Move;
+use crate::player::Player;
+use crate::random::Random;
+
+/// Options for `Mcts`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MctsOptions {
+    /// Number of playouts per search.
+    pub iterations: u32,
+    /// Exploration constant of the UCT formula. Larger values explore more.
+    pub exploration: f64,
+    /// Equivalence parameter of RAVE (rapid action value estimation). The value of a move mixes its own statistics
+    /// with its all-moves-as-first (AMAF) statistics with the weight `sqrt(k / (3 * visits + k))`, i.e. the AMAF
+    /// statistics dominate until the move has been visited about `k` times. 0 disables RAVE; the AMAF statistics are
+    /// collected and reported anyway.
+    pub rave_equivalence: f64,
+    /// Seed for the random number generator. Searches with equal options on equal positions give equal results.
+    pub seed: u64,
+    /// Number of threads. With more than one thread, each thread searches its own tree with a share of the iterations
+    /// and a different seed, and the statistics of the root moves are merged (root parallelization).
+    /// Results are reproducible for a fixed number of threads, but differ between different numbers of threads.
+    pub threads: usize,
+}
+
+impl Default for MctsOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 1000,
+            exploration: 1.0,
+            rave_equivalence: 0.0,
+            seed: 0,
+            threads: 1,
+        }
+    }
+}
+
+/// Search statistics of a single move, see `Analysis`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MoveAnalysis {
+    pub coords: Coords,
+    /// Number of playouts that started with this move.
+    pub visits: u32,
+    /// Fraction of these playouts won by the player making this move.
+    pub value: f64,
+    /// Number of playouts in which the player made this move at any time after the current position (AMAF).
+    pub rave_visits: u32,
+    /// Fraction of the AMAF playouts won by the player making this move, or 0 if `rave_visits` is 0.
+    pub rave_value: f64,
+}
+
+/// The result of a search, see `Mcts::analyze`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    /// Statistics of all moves that have been searched, sorted by visits (most visited first).
+    pub moves: Vec<MoveAnalysis>,
+}
+
+impl Analysis {
+    /// Return the most visited move, or None if the game has ended.
+    pub fn best_move(&self) -> Option<Coords> {
+        self.moves.first().map(|analysis| analysis.coords)
+    }
+
+    /// Return the visit count of each cell of a board with the given size, row by row.
+    pub fn visit_counts(&self, size: CoordValue) -> Vec<u32> {
+        let mut counts = vec![0; size as usize * size as usize];
+        for analysis in &self.moves {
+            counts
+                [analysis.coords.row as usize * size as usize + analysis.coords.column as usize] =
+                analysis.visits;
+        }
+        counts
+    }
+}
+
+struct Node {
+    coords: Option<Coords>,
+    // The player who played `coords`
+    player: Option<Color>,
+    children: Vec<usize>,
+    // Moves that have not yet been added as children. None until the node is expanded for the first time.
+    untried_moves: Option<Vec<Coords>>,
+    visits: u32,
+    wins: f64,
+    // All-moves-as-first statistics: playouts through the parent in which `player` played `coords` at any time
+    rave_visits: u32,
+    rave_wins: f64,
+}
+
+impl Node {
+    fn new(coords: Option<Coords>, player: Option<Color>) -> Self {
+        Self {
+            coords,
+            player,
+            children: vec![],
+            untried_moves: None,
+            visits: 0,
+            wins: 0.0,
+            rave_visits: 0,
+            rave_wins: 0.0,
+        }
+    }
+}
+
+/// A computer player using Monte-Carlo tree search with UCT and random playouts.
+///
+/// Since the board can always be filled completely without changing the winner, playouts simply fill all empty cells
+/// in random order and check the connection afterwards. Swapping is not considered.
+///
+/// For the same reason, the search also keeps all-moves-as-first statistics of each move, which can speed up the
+/// search considerably (RAVE, see `MctsOptions::rave_equivalence`).
+///
+/// ```
+/// # use hexgame::{Coords, Game, Mcts, MctsOptions, Player};
+/// let mut game = Game::new(5);
+/// let mut mcts = Mcts::new(MctsOptions { iterations: 200, ..MctsOptions::default() });
+///
+/// let analysis = mcts.analyze(&game);
+/// assert_eq!(analysis.moves.iter().map(|m| m.visits).sum::<u32>(), 200);
+///
+/// let mv = mcts.choose_move(&game).unwrap();
+/// game.play_move(mv).unwrap();
+/// ```
+pub struct Mcts {
+    options: MctsOptions,
+    random: Random,
+}
+
+impl Mcts {
+    pub fn new(options: MctsOptions) -> Self {
+        Self {
+            options,
+            random: Random::new(options.seed),
+        }
+    }
+
+    /// Return the options of this search.
+    pub fn get_options(&self) -> &MctsOptions {
+        &self.options
+    }
+
+    /// Search the current position of the given game and return statistics of all searched moves.
+    pub fn analyze(&mut self, game: &Game) -> Analysis {
+        if game.get_current_player().is_none() {
+            return Analysis { moves: vec![] };
+        }
+        if self.options.threads > 1 {
+            return self.analyze_in_parallel(game);
+        }
+
+        let mut nodes = vec![Node::new(None, None)];
+        for _ in 0..self.options.iterations {
+            self.iterate(&mut nodes, game);
+        }
+
+        let mut moves: Vec<MoveAnalysis> = nodes[0]
+            .children
+            .iter()
+            .map(|child| {
+                let node = &nodes[*child];
+                MoveAnalysis {
+                    coords: node.coords.unwrap(),
+                    visits: node.visits,
+                    value: node.wins / node.visits as f64,
+                    rave_visits: node.rave_visits,
+                    rave_value: rave_value(node),
+                }
+            })
+            .collect();
+        moves.sort_by_key(|analysis| std::cmp::Reverse(analysis.visits));
+        Analysis { moves }
+    }
+
+    fn analyze_in_parallel(&mut self, game: &Game) -> Analysis {
+        let threads = self.options.threads as u32;
+        let searches: Vec<Mcts> = (0..threads)
+            .map(|i| {
+                let share = self.options.iterations / threads;
+                let remainder = self.options.iterations % threads;
+                Mcts::new(MctsOptions {
+                    iterations: share + u32::from(i < remainder),
+                    seed: self.random.next_u64(),
+                    threads: 1,
+                    ..self.options
+                })
+            })
+            .collect();
+
+        // Boards are not Sync, so each thread gets its own copy of the game.
+        let analyses: Vec<Analysis> = std::thread::scope(|scope| {
+            let handles: Vec<_> = searches
+                .into_iter()
+                .map(|mut search| {
+                    let game = game.clone();
+                    scope.spawn(move || search.analyze(&game))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Search thread panicked"))
+                .collect()
+        });
+
+        merge_analyses(&analyses)
+    }
+
+    fn iterate(&mut self, nodes: &mut Vec<Node>, root_game: &Game) {
+        let mut game = root_game.clone();
+        let mut path = vec![0];
+
+        // Selection and expansion
+        while let Status::Ongoing(player) = game.get_status() {
+            let index = *path.last().unwrap();
+            let random = &mut self.random;
+            let untried_moves = nodes[index].untried_moves.get_or_insert_with(|| {
+                let mut cells = game.get_board().get_empty_cells();
+                random.shuffle(&mut cells);
+                cells
+            });
+
+            if let Some(coords) = untried_moves.pop() {
+                game.play(coords).expect("Untried moves are empty cells");
+                nodes.push(Node::new(Some(coords), Some(player)));
+                let child = nodes.len() - 1;
+                nodes[index].children.push(child);
+                path.push(child);
+                break;
+            }
+
+            let child = self.select_child(nodes, index);
+            game.play(nodes[child].coords.unwrap())
+                .expect("Children are empty cells");
+            path.push(child);
+        }
+
+        let (winner, final_board) = match game.get_status() {
+            Status::Finished(winner) => (winner, game.get_board().clone()),
+            Status::Ongoing(player) => self.playout(game.get_board(), player),
+        };
+
+        for index in path {
+            let node = &mut nodes[index];
+            node.visits += 1;
+            if node.player == Some(winner) {
+                node.wins += 1.0;
+            }
+
+            // Since the order of the stones hardly matters in Hex, each stone placed after this node in the tree or in
+            // the playout counts as if it had been played first. The cells of the children are empty in this node,
+            // so the final board tells who has played them.
+            for i in 0..nodes[index].children.len() {
+                let child = nodes[index].children[i];
+                let child = &mut nodes[child];
+                if final_board.get_color(child.coords.unwrap()) == child.player {
+                    child.rave_visits += 1;
+                    if child.player == Some(winner) {
+                        child.rave_wins += 1.0;
+                    }
+                }
+            }
+        }
+    }
+
+    fn select_child(&self, nodes: &[Node], index: usize) -> usize {
+        let log_visits = (nodes[index].visits as f64).ln();
+        let k = self.options.rave_equivalence;
+        let uct = |child: usize| {
+            let node = &nodes[child];
+            let visits = node.visits as f64;
+            let mut value = node.wins / visits;
+            if k > 0.0 && node.rave_visits > 0 {
+                let beta = (k / (3.0 * visits + k)).sqrt();
+                value = (1.0 - beta) * value + beta * rave_value(node);
+            }
+            value + self.options.exploration * (log_visits / visits).sqrt()
+        };
+
+        *nodes[index]
+            .children
+            .iter()
+            .max_by(|a, b| uct(**a).total_cmp(&uct(**b)))
+            .expect("Nodes of ongoing games have children")
+    }
+
+    /// Fill all empty cells in random order, starting with `player`, and return the winner and the filled board.
+    fn playout(&mut self, board: &Board, player: Color) -> (Color, Board) {
+        let mut board = board.clone();
+        let mut cells = board.get_empty_cells();
+        self.random.shuffle(&mut cells);
+
+        let mut color = player;
+        for coords in cells {
+            board.play(coords, color).expect("Cell is empty");
+            color = color.opponent_color();
+        }
+
+        let [edge, opposite_edge] = get_edges_of_color(Color::Black);
+        let winner = if board.is_in_same_set(edge, opposite_edge) {
+            Color::Black
+        } else {
+            Color::White
+        };
+        (winner, board)
+    }
+}
+
+/// Sum up the statistics of each move over several searches of the same position.
+fn merge_analyses(analyses: &[Analysis]) -> Analysis {
+    let mut moves: Vec<MoveAnalysis> = vec![];
+    for analysis in analyses {
+        for mv in &analysis.moves {
+            let index = match moves.iter().position(|other| other.coords == mv.coords) {
+                Some(index) => index,
+                None => {
+                    moves.push(MoveAnalysis {
+                        coords: mv.coords,
+                        visits: 0,
+                        value: 0.0,
+                        rave_visits: 0,
+                        rave_value: 0.0,
+                    });
+                    moves.len() - 1
+                }
+            };
+            // Temporarily sum up wins instead of fractions
+            let merged = &mut moves[index];
+            merged.visits += mv.visits;
+            merged.value += mv.value * mv.visits as f64;
+            merged.rave_visits += mv.rave_visits;
+            merged.rave_value += mv.rave_value * mv.rave_visits as f64;
+        }
+    }
+
+    for mv in moves.iter_mut() {
+        if mv.visits > 0 {
+            mv.value /= mv.visits as f64;
+        }
+        if mv.rave_visits > 0 {
+            mv.rave_value /= mv.rave_visits as f64;
+        }
+    }
+    moves.sort_by_key(|analysis| std::cmp::Reverse(analysis.visits));
+    Analysis { moves }
+}
+
+fn rave_value(node: &Node) -> f64 {
+    if node.rave_visits > 0 {
+        node.rave_wins / node.rave_visits as f64
+    } else {
+        0.0
+    }
+}
+
+impl Player for Mcts {
+    fn choose_move(&mut self, game: &Game) -> Option<Move> {
+        self.analyze(game).best_move().map(Move::Place)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(iterations: u32) -> MctsOptions {
+        MctsOptions {
+            iterations,
+            ..MctsOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_analyze_counts_all_iterations() {
+        let analysis = Mcts::new(options(100)).analyze(&Game::new(3));
+
+        assert_eq!(analysis.moves.len(), 9);
+        assert_eq!(analysis.moves.iter().map(|m| m.visits).sum::<u32>(), 100);
+        assert!(analysis
+            .moves
+            .windows(2)
+            .all(|pair| pair[0].visits >= pair[1].visits));
+        assert_eq!(analysis.visit_counts(3).iter().sum::<u32>(), 100);
+    }
+
+    #[test]
+    fn test_finds_winning_move() {
+        // Black wins immediately by playing b2.
+        let game = Game::load(
+            vec![
+                vec![None, Some(Color::Black), Some(Color::White)],
+                vec![Some(Color::White), None, Some(Color::White)],
+                vec![None, Some(Color::Black), None],
+            ],
+            Some(Color::Black),
+        )
+        .unwrap();
+
+        let analysis = Mcts::new(options(300)).analyze(&game);
+
+        assert_eq!(analysis.best_move(), Some(Coords::new(1, 1)));
+        assert_eq!(analysis.moves[0].value, 1.0);
+    }
+
+    #[test]
+    fn test_plays_center_on_small_board() {
+        // The center is the strongest opening move on 3x3.
+        let mut mcts = Mcts::new(options(20_000));
+        assert_eq!(
+            mcts.choose_move(&Game::new(3)),
+            Some(Move::Place(Coords::new(1, 1)))
+        );
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let game = Game::new(5);
+        let analysis = Mcts::new(options(200)).analyze(&game);
+        let other_analysis = Mcts::new(options(200)).analyze(&game);
+
+        assert_eq!(analysis, other_analysis);
+    }
+
+    #[test]
+    fn test_rave_statistics() {
+        let analysis = Mcts::new(options(200)).analyze(&Game::new(3));
+
+        for mv in &analysis.moves {
+            // Each playout fills all cells, so a move is played (early or late) in about half of all playouts.
+            assert!(mv.rave_visits >= mv.visits);
+            assert!(mv.rave_visits < 200);
+            assert!((0.0..=1.0).contains(&mv.rave_value));
+        }
+        // Black places 5 of the 9 stones in every playout (fewer if the game ends in the tree or the move has not been
+        // added to the tree yet).
+        let rave_visits: u32 = analysis.moves.iter().map(|m| m.rave_visits).sum();
+        assert!(rave_visits <= 5 * 200);
+        assert!(rave_visits > 4 * 200);
+    }
+
+    #[test]
+    fn test_rave_finds_winning_move() {
+        // Black wins immediately by playing b2.
+        let game = Game::load(
+            vec![
+                vec![None, Some(Color::Black), Some(Color::White)],
+                vec![Some(Color::White), None, Some(Color::White)],
+                vec![None, Some(Color::Black), None],
+            ],
+            Some(Color::Black),
+        )
+        .unwrap();
+        let options = MctsOptions {
+            rave_equivalence: 1000.0,
+            ..options(300)
+        };
+
+        let analysis = Mcts::new(options).analyze(&game);
+
+        assert_eq!(analysis.best_move(), Some(Coords::new(1, 1)));
+    }
+
+    #[test]
+    fn test_parallel_search() {
+        let game = Game::new(5);
+        let options = MctsOptions {
+            threads: 4,
+            ..options(403)
+        };
+
+        let analysis = Mcts::new(options).analyze(&game);
+        let other_analysis = Mcts::new(options).analyze(&game);
+
+        assert_eq!(analysis.moves.iter().map(|m| m.visits).sum::<u32>(), 403);
+        assert!(analysis
+            .moves
+            .windows(2)
+            .all(|pair| pair[0].visits >= pair[1].visits));
+        assert_eq!(analysis, other_analysis);
+    }
+
+    #[test]
+    fn test_merge_analyses() {
+        let analysis = |visits, value| Analysis {
+            moves: vec![MoveAnalysis {
+                coords: Coords::new(0, 0),
+                visits,
+                value,
+                rave_visits: 2 * visits,
+                rave_value: value,
+            }],
+        };
+
+        let merged = merge_analyses(&[analysis(1, 1.0), analysis(3, 0.0)]);
+
+        assert_eq!(merged.moves.len(), 1);
+        assert_eq!(merged.moves[0].visits, 4);
+        assert_eq!(merged.moves[0].value, 0.25);
+        assert_eq!(merged.moves[0].rave_visits, 8);
+        assert_eq!(merged.moves[0].rave_value, 0.25);
+    }
+
+    #[test]
+    fn test_finished_game() {
+        let mut game = Game::new(2);
+        game.resign(Color::Black).unwrap();
+        let mut mcts = Mcts::new(options(10));
+
+        assert!(mcts.analyze(&game).moves.is_empty());
+        assert_eq!(mcts.choose_move(&game), None);
+    }
+}
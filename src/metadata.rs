@@ -0,0 +1,65 @@
+/// Information about one of the players of a game.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerInfo {
+    /// Name of the player.
+    pub name: Option<String>,
+    /// Rating of the player, e.g. an Elo rating.
+    pub rating: Option<u32>,
+}
+
+impl PlayerInfo {
+    const EMPTY: PlayerInfo = PlayerInfo {
+        name: None,
+        rating: None,
+    };
+}
+
+/// Metadata of a game that is useful for archives, e.g. player names, event and date.
+///
+/// The metadata of a game can be accessed via `Game::get_metadata` and `Game::get_metadata_mut`.
+/// It is preserved by the JSON `Serialization`. Note that the result of a game is not part of the metadata, see `Game::get_result`.
+///
+/// ```
+/// # use hexgame::Game;
+/// let mut game = Game::new(11);
+/// let metadata = game.get_metadata_mut();
+/// metadata.black.name = Some("Alice".to_string());
+/// metadata.white.rating = Some(1850);
+/// metadata.event = Some("Hex Open".to_string());
+///
+/// assert_eq!(game.get_metadata().black.name.as_deref(), Some("Alice"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct GameMetadata {
+    /// The player playing Black.
+    pub black: PlayerInfo,
+    /// The player playing White.
+    pub white: PlayerInfo,
+    /// Name of the event (e.g. a tournament) where the game was played.
+    pub event: Option<String>,
+    /// Date when the game was played. The format is up to the user, but ISO 8601 (e.g. "2021-03-14") is recommended.
+    pub date: Option<String>,
+    /// Whether the game is played with the swap rule. If true, White may swap after Black's first move (see `Game::swap`).
+    pub swap_rule: bool,
+    /// A free-form comment on the game.
+    pub comment: Option<String>,
+}
+
+impl GameMetadata {
+    pub(crate) const EMPTY: GameMetadata = GameMetadata {
+        black: PlayerInfo::EMPTY,
+        white: PlayerInfo::EMPTY,
+        event: None,
+        date: None,
+        swap_rule: false,
+        comment: None,
+    };
+
+    /// Return whether all fields are empty.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::EMPTY
+    }
+}
@@ -0,0 +1,92 @@
+use crate::coords::{Coords, ParseCoordsError};
+use std::fmt;
+
+/// A single move in a game of Hex.
+///
+/// Moves can be converted to and from strings: Stones are written in "c4" format (see `Coords`), the swap move is written as "swap".
+/// ```
+/// # use hexgame::{Coords, Move};
+/// let moves: Vec<Move> = ["c4", "swap"].iter().map(|s| s.parse().unwrap()).collect();
+/// assert_eq!(moves, vec![Move::Place(Coords::new(3, 2)), Move::Swap]);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Move {
+    /// The current player places a stone at the given coordinates.
+    Place(Coords),
+    /// The second player swaps (see `Game::swap`).
+    Swap,
+}
+
+impl From<Coords> for Move {
+    fn from(coords: Coords) -> Move {
+        Move::Place(coords)
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Move::Place(coords) => write!(f, "{}", coords),
+            Move::Swap => write!(f, "swap"),
+        }
+    }
+}
+
+impl std::str::FromStr for Move {
+    type Err = ParseCoordsError;
+
+    /// Parse a move from "c4" format or "swap".
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string == "swap" {
+            Ok(Move::Swap)
+        } else {
+            string.parse().map(Move::Place)
+        }
+    }
+}
+
+/// With the `serde` feature, moves are serialized as strings like "c4" or "swap".
+#[cfg(feature = "serde")]
+impl serde::Serialize for Move {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Move {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        string.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(Move::Place(Coords::new(12, 5)).to_string(), "f13");
+        assert_eq!(Move::Swap.to_string(), "swap");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "a1".parse::<Move>().unwrap(),
+            Move::Place(Coords::new(0, 0))
+        );
+        assert_eq!("swap".parse::<Move>().unwrap(), Move::Swap);
+        assert!("swapp".parse::<Move>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let moves = vec![Move::Place(Coords::new(0, 1)), Move::Swap];
+        let string = serde_json::to_string(&moves).unwrap();
+        assert_eq!(string, r#"["b1","swap"]"#);
+        assert_eq!(serde_json::from_str::<Vec<Move>>(&string).unwrap(), moves);
+    }
+}
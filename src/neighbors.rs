@@ -5,7 +5,7 @@ use std::iter::Iterator;
 
 pub fn get_neighbors(cells: &HexCells, index: Index) -> impl Iterator<Item = Index> {
     let size = cells.size as Index;
-    let left_neighbor = if index % size == 0 {
+    let left_neighbor = if index.is_multiple_of(size) {
         cells.index_from_edge(Edge::Left)
     } else {
         index - 1
@@ -35,7 +35,7 @@ pub fn get_neighbors(cells: &HexCells, index: Index) -> impl Iterator<Item = Ind
         index + size
     };
 
-    let bottom_left_neighbor = if index < size * (size - 1) && index % size > 0 {
+    let bottom_left_neighbor = if index < size * (size - 1) && !index.is_multiple_of(size) {
         Some(index + size - 1)
     } else {
         None
@@ -0,0 +1,227 @@
+use crate::coords::{CoordValue, Coords};
+use crate::evaluator::{Evaluation, Evaluator};
+use crate::features::FeatureOptions;
+use crate::game::Game;
+use crate::random::Random;
+
+/// A tiny fully connected network with one hidden layer, a policy head and a value head.
+///
+/// The input are the features of `Game::to_features` with default options. The hidden layer uses ReLU, the policy head
+/// a softmax over the empty cells and the value head tanh. This network is mainly a reference implementation of
+/// `Evaluator` and useful for tests; real experiments will use a proper machine learning framework.
+///
+/// The weights are stored in a single vector in this order: hidden weights (hidden size × input size, row by row),
+/// hidden biases, policy weights (cells × hidden size), policy biases, value weights (hidden size) and the value bias.
+///
+/// ```
+/// # use hexgame::{DenseNetwork, Evaluator, Game};
+/// let mut network = DenseNetwork::random(5, 16, 42);
+/// let evaluation = network.evaluate(&Game::new(5));
+///
+/// assert_eq!(evaluation.priors.len(), 25);
+/// assert!(evaluation.value > -1.0 && evaluation.value < 1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseNetwork {
+    size: CoordValue,
+    hidden_size: usize,
+    weights: Vec<f32>,
+}
+
+impl DenseNetwork {
+    /// Create a network for boards of the given size with random weights.
+    pub fn random(size: CoordValue, hidden_size: usize, seed: u64) -> Self {
+        let mut random = Random::new(seed);
+        let input_size = input_size(size);
+        let weights = (0..weight_count(size, hidden_size))
+            .map(|_| {
+                // Uniform initialization scaled by the input size of the hidden layer
+                let scale = 1.0 / (input_size as f64).sqrt();
+                ((2.0 * random.next_f64() - 1.0) * scale) as f32
+            })
+            .collect();
+        Self {
+            size,
+            hidden_size,
+            weights,
+        }
+    }
+
+    /// Create a network from the given weights (see above for the order).
+    /// Return None if the number of weights does not match `DenseNetwork::weight_count`.
+    pub fn from_weights(size: CoordValue, hidden_size: usize, weights: Vec<f32>) -> Option<Self> {
+        if weights.len() != weight_count(size, hidden_size) {
+            return None;
+        }
+        Some(Self {
+            size,
+            hidden_size,
+            weights,
+        })
+    }
+
+    /// Return the number of weights of a network with the given board size and hidden size.
+    pub fn weight_count(size: CoordValue, hidden_size: usize) -> usize {
+        weight_count(size, hidden_size)
+    }
+
+    /// Return the board size this network was built for.
+    pub fn get_size(&self) -> CoordValue {
+        self.size
+    }
+
+    /// Return the number of hidden units.
+    pub fn get_hidden_size(&self) -> usize {
+        self.hidden_size
+    }
+
+    /// Return all weights (see above for the order).
+    pub fn get_weights(&self) -> &[f32] {
+        &self.weights
+    }
+}
+
+fn input_size(size: CoordValue) -> usize {
+    let options = FeatureOptions::default();
+    let padded_size = size as usize + 2 * options.padding;
+    options.plane_count() * padded_size * padded_size
+}
+
+fn weight_count(size: CoordValue, hidden_size: usize) -> usize {
+    let cells = size as usize * size as usize;
+    (input_size(size) + 1) * hidden_size + (hidden_size + 1) * cells + hidden_size + 1
+}
+
+/// Compute `weights * input + biases`, where `weights` is a matrix with `input.len()` columns stored row by row and
+/// followed by the biases. Return the result and the remaining weights.
+fn dense<'a>(weights: &'a [f32], input: &[f32], output_size: usize) -> (Vec<f32>, &'a [f32]) {
+    let (matrix, rest) = weights.split_at(output_size * input.len());
+    let (biases, rest) = rest.split_at(output_size);
+    let output = matrix
+        .chunks(input.len())
+        .zip(biases)
+        .map(|(row, bias)| {
+            row.iter()
+                .zip(input)
+                .map(|(weight, x)| weight * x)
+                .sum::<f32>()
+                + bias
+        })
+        .collect();
+    (output, rest)
+}
+
+impl Evaluator for DenseNetwork {
+    /// Panics if the size of the game's board differs from the size of the network.
+    fn evaluate(&mut self, game: &Game) -> Evaluation {
+        assert_eq!(
+            game.get_board().size(),
+            self.size,
+            "The network was built for a different board size"
+        );
+        let features = game.to_features(&FeatureOptions::default());
+
+        let (mut hidden, rest) = dense(&self.weights, &features.data, self.hidden_size);
+        for x in hidden.iter_mut() {
+            *x = x.max(0.0);
+        }
+        let cells = self.size as usize * self.size as usize;
+        let (logits, rest) = dense(rest, &hidden, cells);
+        let (value, _) = dense(rest, &hidden, 1);
+
+        // The features are normalized by a symmetry. Since all symmetries are involutions, applying it again maps
+        // the outputs back to the board.
+        let board = game.get_board();
+        let moves: Vec<(Coords, f32)> = board
+            .get_empty_cells()
+            .into_iter()
+            .map(|coords| {
+                let normalized = features.symmetry.apply_to_coords(coords, self.size);
+                let index =
+                    normalized.row as usize * self.size as usize + normalized.column as usize;
+                (coords, logits[index])
+            })
+            .collect();
+        let max_logit = moves
+            .iter()
+            .map(|(_, logit)| *logit)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let exponentials: Vec<f32> = moves
+            .iter()
+            .map(|(_, logit)| (logit - max_logit).exp())
+            .collect();
+        let sum: f32 = exponentials.iter().sum();
+
+        Evaluation {
+            priors: moves
+                .iter()
+                .zip(exponentials)
+                .map(|((coords, _), exponential)| (*coords, exponential / sum))
+                .collect(),
+            value: value[0].tanh(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    #[test]
+    fn test_weight_count() {
+        // 4 planes of 4x4 (board of 2x2 with padding) → 64 inputs
+        assert_eq!(DenseNetwork::weight_count(2, 3), 65 * 3 + 4 * 4 + 4);
+        assert_eq!(
+            DenseNetwork::random(2, 3, 0).get_weights().len(),
+            DenseNetwork::weight_count(2, 3)
+        );
+        assert!(DenseNetwork::from_weights(2, 3, vec![0.0; 10]).is_none());
+    }
+
+    #[test]
+    fn test_priors_are_normalized() {
+        let mut game = Game::new(4);
+        game.play(Coords::new(1, 2)).unwrap();
+        let mut network = DenseNetwork::random(4, 8, 3);
+
+        let evaluation = network.evaluate(&game);
+
+        assert_eq!(evaluation.priors.len(), 15);
+        let sum: f32 = evaluation.priors.iter().map(|(_, prior)| prior).sum();
+        assert!((sum - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_handcrafted_weights() {
+        // Network with a single hidden unit: The policy prefers cells by the bias only, the value is tanh(1).
+        let size = 2;
+        let inputs = 64;
+        let mut weights = vec![0.0; DenseNetwork::weight_count(size, 1)];
+        weights[inputs] = 1.0; // hidden bias
+        let policy_biases = inputs + 1 + 4;
+        weights[policy_biases + 1] = 10.0; // the normalized cell (0, 1)
+        weights[policy_biases + 4] = 1.0; // value weight
+        let mut network = DenseNetwork::from_weights(size, 1, weights).unwrap();
+
+        let black_evaluation = network.evaluate(&Game::new(2));
+        let best = |evaluation: &Evaluation| {
+            evaluation
+                .priors
+                .iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap()
+                .0
+        };
+        assert_eq!(best(&black_evaluation), Coords::new(0, 1));
+        assert!((black_evaluation.value - 1.0f32.tanh()).abs() < 1e-6);
+
+        // For White, the output is transposed back to the board.
+        let white_game = Game::load(
+            vec![vec![None, None], vec![None, Some(Color::Black)]],
+            Some(Color::White),
+        )
+        .unwrap();
+        assert_eq!(best(&network.evaluate(&white_game)), Coords::new(1, 0));
+    }
+}
@@ -0,0 +1,272 @@
+use crate::board::{MAX_BOARD_SIZE, MIN_BOARD_SIZE};
+use crate::coords::{to_column_char, CoordValue, Coords};
+use crate::errors::ParseNotationError;
+use crate::game::Game;
+use crate::moves::Move;
+
+const TRMPH_URL: &str = "https://trmph.com/hex/board#";
+
+// trmph: "https://trmph.com/hex/board#11,a1b2c3". The URL is optional when parsing.
+// Cells are written in "c4" format without separators. A swap is written by repeating the first cell.
+pub fn parse_trmph(string: &str) -> Result<Game, ParseNotationError> {
+    let mut offset = string.len() - string.trim_start().len();
+    let mut notation = string.trim();
+    if let Some(index) = notation.find('#') {
+        offset += index + 1;
+        notation = &notation[index + 1..];
+    }
+
+    let (size_string, cells) = notation.split_once(',').unwrap_or((notation, ""));
+    let size = parse_size(size_string)?;
+    offset += size_string.len() + 1;
+
+    let mut moves = vec![];
+    for (position, token) in trmph_tokens(cells) {
+        let coords: Coords = token.parse().map_err(|_| {
+            ParseNotationError::UnrecognizedToken(offset + position, token.to_string())
+        })?;
+        let is_swap = matches!(moves.as_slice(), [Move::Place(first)] if *first == coords);
+        moves.push(if is_swap {
+            Move::Swap
+        } else {
+            Move::Place(coords)
+        });
+    }
+
+    replay(size, &moves)
+}
+
+pub fn to_trmph(game: &Game) -> String {
+    let size = game.get_board().size();
+    let moves = game.get_moves();
+    if moves.is_empty() {
+        return format!("{}{}", TRMPH_URL, size);
+    }
+
+    let cells: String = moves
+        .iter()
+        .map(|mv| match mv {
+            Move::Place(coords) => coords.to_string(),
+            // Only the first stone can be swapped
+            Move::Swap => moves[0].to_string(),
+        })
+        .collect();
+    format!("{}{},{}", TRMPH_URL, size, cells)
+}
+
+/// Split a trmph cell list into tokens consisting of a letter followed by digits.
+/// Any other characters are put into tokens of their own so that they are reported as errors.
+fn trmph_tokens(cells: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut start = 0;
+    for (position, c) in cells.char_indices().skip(1) {
+        if !c.is_ascii_digit() {
+            tokens.push((start, &cells[start..position]));
+            start = position;
+        }
+    }
+    if start < cells.len() {
+        tokens.push((start, &cells[start..]));
+    }
+    tokens
+}
+
+// Little Golem: Cells are written as two letters (column and row), separated by whitespace, e.g. "cd swap ef".
+pub fn parse_little_golem(size: CoordValue, string: &str) -> Result<Game, ParseNotationError> {
+    let moves = whitespace_tokens(string)
+        .map(|(position, token)| {
+            parse_little_golem_move(token)
+                .ok_or_else(|| ParseNotationError::UnrecognizedToken(position, token.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    replay(size, &moves)
+}
+
+pub fn to_little_golem(game: &Game) -> String {
+    game.get_moves()
+        .iter()
+        .map(|mv| match mv {
+            Move::Place(coords) => {
+                format!(
+                    "{}{}",
+                    to_column_char(coords.column),
+                    to_column_char(coords.row)
+                )
+            }
+            Move::Swap => "swap".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_little_golem_move(token: &str) -> Option<Move> {
+    if token == "swap" {
+        return Some(Move::Swap);
+    }
+
+    match token.as_bytes() {
+        [column @ b'a'..=b'z', row @ b'a'..=b'z'] => {
+            Some(Move::Place(Coords::new(row - b'a', column - b'a')))
+        }
+        _ => None,
+    }
+}
+
+fn whitespace_tokens(string: &str) -> impl Iterator<Item = (usize, &str)> {
+    string.split_whitespace().map(move |token| {
+        // `split_whitespace` returns subslices, so the position can be computed from the pointers.
+        (token.as_ptr() as usize - string.as_ptr() as usize, token)
+    })
+}
+
+fn parse_size(string: &str) -> Result<CoordValue, ParseNotationError> {
+    string
+        .trim()
+        .parse()
+        .ok()
+        .filter(|size| (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(size))
+        .ok_or_else(|| ParseNotationError::InvalidSize(string.to_string()))
+}
+
+fn replay(size: CoordValue, moves: &[Move]) -> Result<Game, ParseNotationError> {
+    if !(MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&size) {
+        return Err(ParseNotationError::InvalidSize(size.to_string()));
+    }
+
+    let mut game = Game::new(size);
+    // Neither notation records whether the swap rule is used, so it is only enabled if the players actually swapped.
+    game.get_metadata_mut().swap_rule = moves.contains(&Move::Swap);
+
+    for (index, mv) in moves.iter().enumerate() {
+        game.play_move(*mv)
+            .map_err(|error| ParseNotationError::InvalidMove(index, error))?;
+    }
+
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::errors::InvalidMove;
+
+    #[test]
+    fn test_parse_trmph() {
+        let game = parse_trmph("https://trmph.com/hex/board#11,a1k11c10").unwrap();
+
+        assert_eq!(game.get_board().size(), 11);
+        assert_eq!(
+            game.get_moves(),
+            &[
+                Move::Place(Coords::new(0, 0)),
+                Move::Place(Coords::new(10, 10)),
+                Move::Place(Coords::new(9, 2)),
+            ]
+        );
+        assert_eq!(game.get_current_player(), Some(Color::White));
+    }
+
+    #[test]
+    fn test_parse_trmph_without_url() {
+        let game = parse_trmph("5,c3").unwrap();
+        assert_eq!(game.get_moves(), &[Move::Place(Coords::new(2, 2))]);
+
+        let game = parse_trmph("5").unwrap();
+        assert!(game.get_moves().is_empty());
+    }
+
+    #[test]
+    fn test_parse_trmph_swap() {
+        let game = parse_trmph("#5,b4b4c3").unwrap();
+
+        assert_eq!(
+            game.get_moves(),
+            &[
+                Move::Place(Coords::new(3, 1)),
+                Move::Swap,
+                Move::Place(Coords::new(2, 2))
+            ]
+        );
+        assert!(game.get_metadata().swap_rule);
+        assert_eq!(
+            game.get_board().get_color(Coords::new(1, 3)),
+            Some(Color::White)
+        );
+    }
+
+    #[test]
+    fn test_trmph_errors() {
+        assert_eq!(
+            parse_trmph("#x,a1").err().unwrap(),
+            ParseNotationError::InvalidSize("x".to_string())
+        );
+        assert_eq!(
+            parse_trmph("#25,a1").err().unwrap(),
+            ParseNotationError::InvalidSize("25".to_string())
+        );
+        assert_eq!(
+            parse_trmph("#5,a1b2-c3").err().unwrap(),
+            ParseNotationError::UnrecognizedToken(7, "-".to_string())
+        );
+        assert_eq!(
+            parse_trmph("#5,a1B2").err().unwrap(),
+            ParseNotationError::UnrecognizedToken(5, "B2".to_string())
+        );
+        assert_eq!(
+            parse_trmph("#5,a1f6").err().unwrap(),
+            ParseNotationError::InvalidMove(1, InvalidMove::OutOfBounds(Coords::new(5, 5)))
+        );
+        assert_eq!(
+            parse_trmph("#5,a1b2a1").err().unwrap(),
+            ParseNotationError::InvalidMove(2, InvalidMove::CellOccupied(Coords::new(0, 0)))
+        );
+    }
+
+    #[test]
+    fn test_to_trmph() {
+        let mut game = Game::new(13);
+        assert_eq!(to_trmph(&game), "https://trmph.com/hex/board#13");
+
+        game.get_metadata_mut().swap_rule = true;
+        game.play(Coords::new(11, 0)).unwrap();
+        game.swap().unwrap();
+        game.play(Coords::new(6, 6)).unwrap();
+
+        let string = to_trmph(&game);
+        assert_eq!(string, "https://trmph.com/hex/board#13,a12a12g7");
+        assert_eq!(parse_trmph(&string).unwrap().get_moves(), game.get_moves());
+    }
+
+    #[test]
+    fn test_little_golem() {
+        let game = parse_little_golem(11, " cd swap\tef ").unwrap();
+
+        assert_eq!(
+            game.get_moves(),
+            &[
+                Move::Place(Coords::new(3, 2)),
+                Move::Swap,
+                Move::Place(Coords::new(5, 4))
+            ]
+        );
+        assert_eq!(to_little_golem(&game), "cd swap ef");
+    }
+
+    #[test]
+    fn test_little_golem_errors() {
+        assert_eq!(
+            parse_little_golem(11, "cd swap c4").err().unwrap(),
+            ParseNotationError::UnrecognizedToken(8, "c4".to_string())
+        );
+        assert_eq!(
+            parse_little_golem(11, "cd cd").err().unwrap(),
+            ParseNotationError::InvalidMove(1, InvalidMove::CellOccupied(Coords::new(3, 2)))
+        );
+        assert_eq!(
+            parse_little_golem(1, "").err().unwrap(),
+            ParseNotationError::InvalidSize("1".to_string())
+        );
+    }
+}
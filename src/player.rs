@@ -0,0 +1,8 @@
+use crate::game::Game;
+use crate::moves::Move;
+
+/// A computer player, e.g. `Mcts`.
+pub trait Player {
+    /// Return the move to play in the current position of the given game, or None if the game has ended.
+    fn choose_move(&mut self, game: &Game) -> Option<Move>;
+}
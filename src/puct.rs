@@ -0,0 +1,376 @@
+use crate::color::Color;
+use crate::coords::Coords;
+use crate::evaluator::{Evaluation, Evaluator};
+use crate::game::{Game, Status};
+use crate::mcts::{Analysis, MoveAnalysis};
+use crate::moves::Move;
+use crate::player::Player;
+
+/// Options for `Puct`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PuctOptions {
+    /// Number of leaf evaluations (including finished games) per search.
+    pub iterations: u32,
+    /// Exploration constant of the PUCT formula. Larger values rely more on the priors.
+    pub exploration: f64,
+    /// Maximum number of leaves that are passed to `Evaluator::evaluate_batch` at once.
+    /// Larger batches are more efficient for models on a GPU, but make the search a little less selective.
+    pub batch_size: usize,
+}
+
+impl Default for PuctOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 800,
+            exploration: 1.5,
+            batch_size: 8,
+        }
+    }
+}
+
+struct Node {
+    coords: Option<Coords>,
+    // The player who played `coords`
+    player: Option<Color>,
+    prior: f64,
+    children: Vec<usize>,
+    expanded: bool,
+    visits: u32,
+    // Sum of the values from the perspective of `player`
+    value_sum: f64,
+    // Number of selections whose evaluation is still pending ("virtual loss")
+    pending: u32,
+}
+
+impl Node {
+    fn new(coords: Option<Coords>, player: Option<Color>, prior: f64) -> Self {
+        Self {
+            coords,
+            player,
+            prior,
+            children: vec![],
+            expanded: false,
+            visits: 0,
+            value_sum: 0.0,
+            pending: 0,
+        }
+    }
+}
+
+/// A computer player using AlphaZero-style tree search: Instead of random playouts, the search asks an `Evaluator`
+/// for move priors and the value of leaf positions and selects moves with the PUCT formula.
+///
+/// To use the evaluator efficiently, leaves are collected in batches. Leaves that are waiting for their evaluation
+/// count as lost ("virtual loss"), so that the next selection in the same batch prefers other moves.
+/// Swapping is not considered. The search is deterministic if the evaluator is.
+///
+/// ```
+/// # use hexgame::{Game, Puct, PuctOptions, UniformEvaluator};
+/// let game = Game::new(5);
+/// let mut puct = Puct::new(PuctOptions { iterations: 100, ..PuctOptions::default() }, UniformEvaluator);
+///
+/// let analysis = puct.analyze(&game);
+/// assert_eq!(analysis.moves.iter().map(|m| m.visits).sum::<u32>(), 100);
+/// ```
+pub struct Puct<E: Evaluator> {
+    options: PuctOptions,
+    evaluator: E,
+}
+
+impl<E: Evaluator> Puct<E> {
+    pub fn new(options: PuctOptions, evaluator: E) -> Self {
+        Self { options, evaluator }
+    }
+
+    /// Return the options of this search.
+    pub fn get_options(&self) -> &PuctOptions {
+        &self.options
+    }
+
+    /// Return the evaluator.
+    pub fn get_evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// Return the evaluator mutably, e.g. to update the weights of a model between searches.
+    pub fn get_evaluator_mut(&mut self) -> &mut E {
+        &mut self.evaluator
+    }
+
+    /// Search the current position of the given game and return statistics of all searched moves.
+    ///
+    /// The value of a move is the expected score in the range 0 (loss) to 1 (win) for the player making it,
+    /// so that it is comparable with the values of `Mcts`.
+    pub fn analyze(&mut self, game: &Game) -> Analysis {
+        let player = match game.get_status() {
+            Status::Ongoing(player) => player,
+            Status::Finished(_) => return Analysis { moves: vec![] },
+        };
+
+        let mut nodes = vec![Node::new(None, None, 1.0)];
+        let evaluation = self.evaluator.evaluate(game);
+        expand(&mut nodes, 0, game, player, &evaluation);
+
+        let mut iterations = 0;
+        while iterations < self.options.iterations {
+            let limit = (self.options.iterations - iterations) as usize;
+            iterations +=
+                self.run_batch(&mut nodes, game, limit.min(self.options.batch_size.max(1)));
+        }
+
+        let mut moves: Vec<MoveAnalysis> = nodes[0]
+            .children
+            .iter()
+            .map(|child| &nodes[*child])
+            .filter(|node| node.visits > 0)
+            .map(|node| MoveAnalysis {
+                coords: node.coords.unwrap(),
+                visits: node.visits,
+                value: (node.value_sum / node.visits as f64 + 1.0) / 2.0,
+                rave_visits: 0,
+                rave_value: 0.0,
+            })
+            .collect();
+        moves.sort_by_key(|analysis| std::cmp::Reverse(analysis.visits));
+        Analysis { moves }
+    }
+
+    /// Select up to `limit` leaves, evaluate them together and back up the values. Return the number of completed
+    /// iterations, which is at least 1.
+    fn run_batch(&mut self, nodes: &mut Vec<Node>, root_game: &Game, limit: usize) -> u32 {
+        let mut completed = 0;
+        let mut leaves: Vec<(Vec<usize>, Game)> = vec![];
+
+        while completed + leaves.len() < limit {
+            let mut game = root_game.clone();
+            let mut path = vec![0];
+            while nodes[*path.last().unwrap()].expanded {
+                if game.get_current_player().is_none() {
+                    break;
+                }
+                let child = self.select_child(nodes, *path.last().unwrap());
+                game.play(nodes[child].coords.unwrap())
+                    .expect("Children are empty cells");
+                path.push(child);
+            }
+            let leaf = *path.last().unwrap();
+
+            if let Status::Finished(winner) = game.get_status() {
+                backup(nodes, &path, winner, 1.0);
+                completed += 1;
+            } else if nodes[leaf].pending > 0 {
+                // This leaf is already part of the batch. Evaluate the batch first.
+                break;
+            } else {
+                for index in &path {
+                    nodes[*index].pending += 1;
+                }
+                leaves.push((path, game));
+            }
+        }
+
+        let games: Vec<Game> = leaves.iter().map(|(_, game)| game.clone()).collect();
+        let evaluations = if games.is_empty() {
+            vec![]
+        } else {
+            self.evaluator.evaluate_batch(&games)
+        };
+        for ((path, game), evaluation) in leaves.into_iter().zip(evaluations) {
+            for index in &path {
+                nodes[*index].pending -= 1;
+            }
+            let player = game.get_current_player().unwrap();
+            expand(nodes, *path.last().unwrap(), &game, player, &evaluation);
+            backup(
+                nodes,
+                &path,
+                player,
+                evaluation.value.clamp(-1.0, 1.0) as f64,
+            );
+            completed += 1;
+        }
+
+        completed as u32
+    }
+
+    fn select_child(&self, nodes: &[Node], index: usize) -> usize {
+        let parent = &nodes[index];
+        let parent_visits = ((parent.visits + parent.pending) as f64).sqrt();
+        let puct = |child: usize| {
+            let node = &nodes[child];
+            let visits = (node.visits + node.pending) as f64;
+            let mean = if visits > 0.0 {
+                // Pending evaluations count as losses.
+                (node.value_sum - node.pending as f64) / visits
+            } else {
+                0.0
+            };
+            mean + self.options.exploration * node.prior * parent_visits / (1.0 + visits)
+        };
+
+        *parent
+            .children
+            .iter()
+            .max_by(|a, b| puct(**a).total_cmp(&puct(**b)))
+            .expect("Nodes of ongoing games have children")
+    }
+}
+
+/// Add children for all empty cells with the (normalized) priors of the evaluation.
+fn expand(
+    nodes: &mut Vec<Node>,
+    index: usize,
+    game: &Game,
+    player: Color,
+    evaluation: &Evaluation,
+) {
+    let board = game.get_board();
+    let size = board.size() as usize;
+    let cell_index = |coords: Coords| coords.row as usize * size + coords.column as usize;
+    // Duplicate entries for a cell are added up.
+    let mut priors = vec![0.0; size * size];
+    for (coords, prior) in evaluation.priors.iter() {
+        if coords.is_on_board_with_size(board.size()) && board.get_color(*coords).is_none() {
+            priors[cell_index(*coords)] += prior.max(0.0) as f64;
+        }
+    }
+    let sum: f64 = priors.iter().sum();
+    let empty_cells = board.get_empty_cells();
+
+    for coords in empty_cells.iter() {
+        let prior = if sum > 0.0 {
+            priors[cell_index(*coords)] / sum
+        } else {
+            1.0 / empty_cells.len() as f64
+        };
+        nodes.push(Node::new(Some(*coords), Some(player), prior));
+        let child = nodes.len() - 1;
+        nodes[index].children.push(child);
+    }
+    nodes[index].expanded = true;
+}
+
+/// Add a visit to all nodes on the path. `value` is the outcome from the perspective of `color`.
+fn backup(nodes: &mut [Node], path: &[usize], color: Color, value: f64) {
+    for index in path {
+        let node = &mut nodes[*index];
+        node.visits += 1;
+        node.value_sum += if node.player == Some(color) {
+            value
+        } else {
+            -value
+        };
+    }
+}
+
+impl<E: Evaluator> Player for Puct<E> {
+    fn choose_move(&mut self, game: &Game) -> Option<Move> {
+        self.analyze(game).best_move().map(Move::Place)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator::UniformEvaluator;
+
+    fn options(iterations: u32, batch_size: usize) -> PuctOptions {
+        PuctOptions {
+            iterations,
+            batch_size,
+            ..PuctOptions::default()
+        }
+    }
+
+    /// Counts the evaluated positions and the sizes of the batches.
+    struct CountingEvaluator {
+        batch_sizes: Vec<usize>,
+    }
+
+    impl Evaluator for CountingEvaluator {
+        fn evaluate(&mut self, game: &Game) -> Evaluation {
+            UniformEvaluator.evaluate(game)
+        }
+
+        fn evaluate_batch(&mut self, games: &[Game]) -> Vec<Evaluation> {
+            self.batch_sizes.push(games.len());
+            games.iter().map(|game| self.evaluate(game)).collect()
+        }
+    }
+
+    #[test]
+    fn test_analyze_counts_all_iterations() {
+        let analysis = Puct::new(options(50, 4), UniformEvaluator).analyze(&Game::new(3));
+
+        assert_eq!(analysis.moves.iter().map(|m| m.visits).sum::<u32>(), 50);
+        assert!(analysis
+            .moves
+            .windows(2)
+            .all(|pair| pair[0].visits >= pair[1].visits));
+    }
+
+    #[test]
+    fn test_batches() {
+        let evaluator = CountingEvaluator {
+            batch_sizes: vec![],
+        };
+        let mut puct = Puct::new(options(100, 8), evaluator);
+        puct.analyze(&Game::new(7));
+
+        let batch_sizes = &puct.get_evaluator().batch_sizes;
+        assert_eq!(batch_sizes.iter().sum::<usize>(), 100);
+        assert!(batch_sizes.iter().all(|size| *size <= 8));
+        // Virtual losses spread the selections, so the batches on an open board are full.
+        assert_eq!(batch_sizes[0], 8);
+    }
+
+    #[test]
+    fn test_finds_winning_move() {
+        // Black wins immediately by playing b2.
+        let game = Game::load(
+            vec![
+                vec![None, Some(Color::Black), Some(Color::White)],
+                vec![Some(Color::White), None, Some(Color::White)],
+                vec![None, Some(Color::Black), None],
+            ],
+            Some(Color::Black),
+        )
+        .unwrap();
+
+        let analysis = Puct::new(options(100, 4), UniformEvaluator).analyze(&game);
+
+        assert_eq!(analysis.best_move(), Some(Coords::new(1, 1)));
+        assert_eq!(analysis.moves[0].value, 1.0);
+    }
+
+    #[test]
+    fn test_follows_priors() {
+        /// Strongly prefers the cell c3.
+        struct BiasedEvaluator;
+        impl Evaluator for BiasedEvaluator {
+            fn evaluate(&mut self, _game: &Game) -> Evaluation {
+                Evaluation {
+                    priors: vec![(Coords::new(2, 2), 1.0)],
+                    value: 0.0,
+                }
+            }
+        }
+
+        let mut puct = Puct::new(options(20, 1), BiasedEvaluator);
+
+        assert_eq!(
+            puct.choose_move(&Game::new(5)),
+            Some(Move::Place(Coords::new(2, 2)))
+        );
+    }
+
+    #[test]
+    fn test_finished_game() {
+        let mut game = Game::new(2);
+        game.resign(Color::Black).unwrap();
+        let mut puct = Puct::new(options(10, 2), UniformEvaluator);
+
+        assert!(puct.analyze(&game).moves.is_empty());
+        assert_eq!(puct.choose_move(&game), None);
+    }
+}
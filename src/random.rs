@@ -0,0 +1,76 @@
+/// A small and fast pseudo-random number generator (SplitMix64).
+///
+/// The tree search only needs reproducible randomness, not cryptographic quality, so we avoid a dependency on `rand`.
+#[derive(Clone, Debug)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Return a random number in the range `0..n`. `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        // The modulo bias is negligible for the small ranges used in this crate.
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Return a random number in the range `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Shuffle the given slice (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            slice.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproducible() {
+        let mut random = Random::new(42);
+        let mut other_random = Random::new(42);
+        let numbers: Vec<u64> = (0..5).map(|_| random.next_u64()).collect();
+        let other_numbers: Vec<u64> = (0..5).map(|_| other_random.next_u64()).collect();
+
+        assert_eq!(numbers, other_numbers);
+        assert_ne!(Random::new(43).next_u64(), numbers[0]);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut random = Random::new(1);
+        for _ in 0..1000 {
+            assert!(random.below(7) < 7);
+            let x = random.next_f64();
+            assert!((0.0..1.0).contains(&x));
+        }
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut random = Random::new(7);
+        let mut numbers: Vec<u32> = (0..20).collect();
+        random.shuffle(&mut numbers);
+
+        assert_ne!(numbers, (0..20).collect::<Vec<_>>());
+        numbers.sort();
+        assert_eq!(numbers, (0..20).collect::<Vec<_>>());
+    }
+}
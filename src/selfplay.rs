@@ -0,0 +1,477 @@
+use crate::binary::{decode_game, encode_game, encoded_game_length};
+use crate::board::StoneMatrix;
+use crate::book::OpeningBook;
+use crate::coords::{CoordValue, Coords};
+use crate::game::{Game, Status};
+use crate::mcts::{Mcts, MctsOptions};
+use crate::random::Random;
+use crate::symmetry::Symmetry;
+use std::io;
+
+/// Options for `SelfPlay`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SelfPlayOptions {
+    /// Size of the board.
+    pub size: CoordValue,
+    /// Number of games to play.
+    pub games: u32,
+    /// Options of the tree search used by both players. The seed is ignored, see `seed`.
+    /// If a search visits no moves (e.g. with zero iterations), a random empty cell is played and no sample is recorded.
+    pub mcts: MctsOptions,
+    /// Number of moves at the beginning of each game that are sampled proportionally to the visit counts
+    /// instead of choosing the most visited move. This makes games more diverse.
+    pub sampled_moves: u32,
+    /// Record each position in all four symmetric variants (see `Symmetry`).
+    pub augment: bool,
+    /// Seed for the random number generators. Self-play with equal options produces equal games.
+    pub seed: u64,
+}
+
+impl Default for SelfPlayOptions {
+    fn default() -> Self {
+        Self {
+            size: 9,
+            games: 100,
+            mcts: MctsOptions::default(),
+            sampled_moves: 4,
+            augment: false,
+            seed: 0,
+        }
+    }
+}
+
+/// A position from self-play together with the search result and the outcome of the game.
+#[derive(Clone)]
+pub struct TrainingSample {
+    /// The position before the move (including the player to move).
+    pub position: Game,
+    /// Visit count of each cell in the search, row by row.
+    pub visits: Vec<u32>,
+    /// 1 if the player to move has won the game, -1 otherwise.
+    pub outcome: i8,
+}
+
+impl TrainingSample {
+    /// Return this sample after applying the given symmetry to the position and the visit counts.
+    /// If the symmetry swaps colors, the player to move changes too, so the outcome remains the same.
+    pub fn transform(&self, symmetry: Symmetry) -> TrainingSample {
+        let board = self.position.get_board();
+        let size = board.size();
+        let stones: StoneMatrix = symmetry.apply_to_board(board).to_stone_matrix();
+        let current_player = self
+            .position
+            .get_current_player()
+            .map(|color| symmetry.apply_to_color(color));
+
+        let mut visits = vec![0; self.visits.len()];
+        for row in 0..size {
+            for column in 0..size {
+                let target = symmetry.apply_to_coords(Coords { row, column }, size);
+                visits[cell_index(target, size)] =
+                    self.visits[cell_index(Coords { row, column }, size)];
+            }
+        }
+
+        TrainingSample {
+            position: Game::load(stones, current_player).expect("Symmetric positions are valid"),
+            visits,
+            outcome: self.outcome,
+        }
+    }
+}
+
+fn cell_index(coords: Coords, size: CoordValue) -> usize {
+    coords.row as usize * size as usize + coords.column as usize
+}
+
+/// Generates training data by letting `Mcts` play against itself.
+///
+/// ```
+/// # use hexgame::{MctsOptions, SampleReader, SampleWriter, SelfPlay, SelfPlayOptions};
+/// let options = SelfPlayOptions {
+///     size: 3,
+///     games: 2,
+///     mcts: MctsOptions { iterations: 50, ..MctsOptions::default() },
+///     ..SelfPlayOptions::default()
+/// };
+/// let mut writer = SampleWriter::new(Vec::new());
+/// SelfPlay::new(options).run(&mut writer).unwrap();
+///
+/// let bytes = writer.into_inner();
+/// let samples = SampleReader::new(bytes.as_slice()).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert!(samples.len() >= 2 * 3); // at least 3 moves per game
+/// ```
+pub struct SelfPlay {
+    options: SelfPlayOptions,
+    book: Option<OpeningBook>,
+}
+
+impl SelfPlay {
+    pub fn new(options: SelfPlayOptions) -> Self {
+        Self {
+            options,
+            book: None,
+        }
+    }
+
+    /// Play positions from the given opening book without searching. Book moves are sampled proportionally to their
+    /// weights and produce no samples.
+    pub fn set_book(&mut self, book: OpeningBook) {
+        self.book = Some(book);
+    }
+
+    /// Play the game with the given index (starting at 0) and return the finished game and its samples.
+    ///
+    /// Each game only depends on the options and its index, so games can be generated in any order or in parallel.
+    pub fn play_game(&self, index: u32) -> (Game, Vec<TrainingSample>) {
+        let mut random =
+            Random::new(self.options.seed ^ (index as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let mut mcts = Mcts::new(MctsOptions {
+            seed: random.next_u64(),
+            ..self.options.mcts
+        });
+
+        let mut game = Game::new(self.options.size);
+        let mut searches: Vec<(Game, Vec<u32>)> = vec![];
+
+        while game.get_current_player().is_some() {
+            if let Some(book) = &self.book {
+                let book_moves: Vec<(Coords, u32)> = book
+                    .get_moves(&game)
+                    .iter()
+                    .map(|mv| (mv.coords, mv.weight))
+                    .filter(|(_, weight)| *weight > 0)
+                    .collect();
+                if !book_moves.is_empty() {
+                    let coords = sample_move(&book_moves, &mut random);
+                    game.play(coords).expect("Book moves are valid");
+                    continue;
+                }
+            }
+
+            let analysis = mcts.analyze(&game);
+            let moves: Vec<(Coords, u32)> = analysis
+                .moves
+                .iter()
+                .map(|mv| (mv.coords, mv.visits))
+                .filter(|(_, visits)| *visits > 0)
+                .collect();
+            if moves.is_empty() {
+                let empty_cells = game.get_board().get_empty_cells();
+                let coords = empty_cells[random.below(empty_cells.len())];
+                game.play(coords).expect("Empty cells are valid moves");
+                continue;
+            }
+
+            let coords = if (game.get_moves().len() as u32) < self.options.sampled_moves {
+                sample_move(&moves, &mut random)
+            } else {
+                analysis.best_move().unwrap()
+            };
+            searches.push((game.clone(), analysis.visit_counts(self.options.size)));
+            game.play(coords).expect("Searched moves are valid");
+        }
+
+        let winner = match game.get_status() {
+            Status::Finished(winner) => winner,
+            Status::Ongoing(_) => unreachable!(),
+        };
+        let samples = searches
+            .into_iter()
+            .flat_map(|(position, visits)| {
+                let outcome = if position.get_current_player() == Some(winner) {
+                    1
+                } else {
+                    -1
+                };
+                let sample = TrainingSample {
+                    position,
+                    visits,
+                    outcome,
+                };
+                self.augment(sample)
+            })
+            .collect();
+
+        (game, samples)
+    }
+
+    fn augment(&self, sample: TrainingSample) -> Vec<TrainingSample> {
+        if !self.options.augment {
+            return vec![sample];
+        }
+        Symmetry::ALL
+            .iter()
+            .map(|symmetry| sample.transform(*symmetry))
+            .collect()
+    }
+
+    /// Play all games and write their samples. Returns the number of samples written.
+    pub fn run<W: io::Write>(&self, writer: &mut SampleWriter<W>) -> io::Result<usize> {
+        let mut count = 0;
+        for index in 0..self.options.games {
+            let (_, samples) = self.play_game(index);
+            for sample in &samples {
+                writer.write(sample)?;
+            }
+            count += samples.len();
+        }
+        writer.flush()?;
+        Ok(count)
+    }
+}
+
+/// Choose one of the moves with a probability proportional to its weight. The sum of the weights must be positive.
+fn sample_move(moves: &[(Coords, u32)], random: &mut Random) -> Coords {
+    let total: u64 = moves.iter().map(|(_, weight)| *weight as u64).sum();
+    assert!(total > 0, "Cannot sample from moves without weight");
+    let mut remaining = random.below(total as usize) as u64;
+    for (coords, weight) in moves {
+        if remaining < *weight as u64 {
+            return *coords;
+        }
+        remaining -= *weight as u64;
+    }
+    unreachable!("The sum of all weights is larger than the sampled number")
+}
+
+/// Writes `TrainingSample`s to a stream in a compact binary format.
+///
+/// Each sample consists of the position in the format of `Game::to_bytes`, the visit count of each cell as 16-bit
+/// little endian integer (clamped to 65535) and the outcome as signed byte. Use `SampleReader` to read the samples.
+pub struct SampleWriter<W: io::Write> {
+    writer: W,
+}
+
+impl<W: io::Write> SampleWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Write a single sample.
+    pub fn write(&mut self, sample: &TrainingSample) -> io::Result<()> {
+        let mut bytes = encode_game(&sample.position);
+        for visits in &sample.visits {
+            let visits = (*visits).min(u16::MAX as u32) as u16;
+            bytes.extend_from_slice(&visits.to_le_bytes());
+        }
+        bytes.push(sample.outcome as u8);
+        self.writer.write_all(&bytes)
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads samples written by `SampleWriter` from a stream.
+///
+/// `SampleReader` is an iterator over the samples. Invalid data is reported as `std::io::Error` of kind `InvalidData`.
+pub struct SampleReader<R: io::Read> {
+    reader: R,
+    buffer: Vec<u8>,
+}
+
+impl<R: io::Read> SampleReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: vec![],
+        }
+    }
+
+    /// Read the next sample. Return None if the stream ends before the next sample.
+    pub fn read(&mut self) -> io::Result<Option<TrainingSample>> {
+        let mut size = [0u8];
+        if self.reader.read(&mut size)? == 0 {
+            return Ok(None);
+        }
+
+        let size = size[0];
+        let game_length = encoded_game_length(size);
+        let cell_count = size as usize * size as usize;
+        self.buffer.clear();
+        self.buffer.resize(game_length + 2 * cell_count + 1, 0);
+        self.buffer[0] = size;
+        self.reader.read_exact(&mut self.buffer[1..])?;
+
+        let position = decode_game(&self.buffer[..game_length])
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let visits = self.buffer[game_length..game_length + 2 * cell_count]
+            .chunks(2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as u32)
+            .collect();
+        let outcome = self.buffer[game_length + 2 * cell_count] as i8;
+
+        Ok(Some(TrainingSample {
+            position,
+            visits,
+            outcome,
+        }))
+    }
+}
+
+impl<R: io::Read> Iterator for SampleReader<R> {
+    type Item = io::Result<TrainingSample>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::moves::Move;
+
+    fn options() -> SelfPlayOptions {
+        SelfPlayOptions {
+            size: 4,
+            games: 2,
+            mcts: MctsOptions {
+                iterations: 30,
+                ..MctsOptions::default()
+            },
+            sampled_moves: 2,
+            augment: false,
+            seed: 5,
+        }
+    }
+
+    #[test]
+    fn test_play_game() {
+        let (game, samples) = SelfPlay::new(options()).play_game(0);
+
+        assert_eq!(samples.len(), game.get_moves().len());
+        let winner = game.get_result().unwrap().winner;
+        for (sample, mv) in samples.iter().zip(game.get_moves()) {
+            assert_eq!(sample.visits.iter().sum::<u32>(), 30);
+            let player = sample.position.get_current_player().unwrap();
+            assert_eq!(sample.outcome == 1, player == winner);
+            // The played move has been searched
+            let coords = match mv {
+                Move::Place(coords) => *coords,
+                Move::Swap => unreachable!(),
+            };
+            assert!(sample.visits[cell_index(coords, 4)] > 0);
+        }
+    }
+
+    #[test]
+    fn test_zero_iterations() {
+        let mut options = options();
+        options.mcts.iterations = 0;
+
+        let (game, samples) = SelfPlay::new(options).play_game(0);
+
+        assert!(game.get_result().is_some());
+        assert!(samples.is_empty());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let self_play = SelfPlay::new(options());
+        let (game, _) = self_play.play_game(1);
+        let (same_game, _) = self_play.play_game(1);
+        let (other_game, _) = self_play.play_game(0);
+
+        assert_eq!(game.get_moves(), same_game.get_moves());
+        assert_ne!(game.get_moves(), other_game.get_moves());
+    }
+
+    #[test]
+    fn test_augment() {
+        let self_play = SelfPlay::new(SelfPlayOptions {
+            augment: true,
+            ..options()
+        });
+        let (game, samples) = self_play.play_game(0);
+
+        assert_eq!(samples.len(), 4 * game.get_moves().len());
+        // The second sample of the first position is rotated by 180°.
+        let first_move_visits = &samples[0].visits;
+        let rotated_visits = &samples[1].visits;
+        assert_eq!(first_move_visits[0], rotated_visits[15]);
+        // The third sample is transposed with White to move.
+        assert_eq!(samples[2].position.get_current_player(), Some(Color::White));
+        assert_eq!(first_move_visits[1], samples[2].visits[4]);
+        // All variants of a position have the same outcome for the player to move.
+        for variants in samples.chunks(4) {
+            let player = variants[0].position.get_current_player();
+            for (sample, symmetry) in variants.iter().zip(Symmetry::ALL) {
+                assert_eq!(sample.outcome, variants[0].outcome);
+                assert_eq!(
+                    sample.position.get_current_player(),
+                    player.map(|player| symmetry.apply_to_color(player))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_book() {
+        let mut book = OpeningBook::new();
+        let mut game = Game::new(4);
+        book.add_move(&game, Coords::new(1, 2), 1);
+        game.play(Coords::new(1, 2)).unwrap();
+        book.add_move(&game, Coords::new(2, 1), 1);
+        let mut self_play = SelfPlay::new(options());
+        self_play.set_book(book);
+
+        let (game, samples) = self_play.play_game(0);
+
+        assert_eq!(
+            game.get_moves()[..2],
+            [
+                Move::Place(Coords::new(1, 2)),
+                Move::Place(Coords::new(2, 1))
+            ]
+        );
+        assert_eq!(samples.len(), game.get_moves().len() - 2);
+        assert_eq!(samples[0].position.get_moves().len(), 2);
+    }
+
+    #[test]
+    fn test_writer_and_reader() {
+        let mut writer = SampleWriter::new(Vec::new());
+        let count = SelfPlay::new(options()).run(&mut writer).unwrap();
+
+        let bytes = writer.into_inner();
+        let samples: Vec<TrainingSample> = SampleReader::new(bytes.as_slice())
+            .collect::<io::Result<_>>()
+            .unwrap();
+
+        assert_eq!(samples.len(), count);
+        let (_, expected_samples) = SelfPlay::new(options()).play_game(0);
+        assert_eq!(samples[0].visits, expected_samples[0].visits);
+        assert_eq!(samples[1].outcome, expected_samples[1].outcome);
+        assert_eq!(
+            samples[1].position.get_board().to_stone_matrix(),
+            expected_samples[1].position.get_board().to_stone_matrix()
+        );
+        // 2 bytes per board size and player, 4 bytes for 16 cells, 32 bytes visits, 1 byte outcome
+        assert_eq!(bytes.len(), count * (2 + 4 + 32 + 1));
+    }
+
+    #[test]
+    fn test_truncated_sample() {
+        let mut writer = SampleWriter::new(Vec::new());
+        let (_, samples) = SelfPlay::new(options()).play_game(0);
+        writer.write(&samples[0]).unwrap();
+        let bytes = writer.into_inner();
+
+        let error = SampleReader::new(&bytes[..bytes.len() - 1])
+            .read()
+            .err()
+            .unwrap();
+
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -1,24 +1,55 @@
 use crate::color::Color;
-use crate::coords::CoordValue;
-use crate::game::Game;
+use crate::coords::{CoordValue, Coords};
+use crate::errors::{InvalidBoard, LoadError};
+use crate::game::{Game, GameResult, Setup, WinReason};
+use crate::metadata::{GameMetadata, PlayerInfo};
+use crate::moves::Move;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::io::{Error, ErrorKind, Result};
 
-const VERSION: u8 = 1;
+/// Versions of the JSON format used by `Serialization`.
+///
+/// All versions can be loaded. By default, games are saved using the latest version.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FormatVersion {
+    /// The original format: Only the current position (stones and current player) and the result.
+    V1 = 1,
+    /// Additionally stores the list of moves (including swaps), the setup position if the game was loaded from a position, and metadata.
+    V2 = 2,
+}
+
+impl FormatVersion {
+    /// The version used by `Serialization::save_to_json`.
+    pub const LATEST: FormatVersion = FormatVersion::V2;
+
+    fn from_number(number: u8) -> Option<Self> {
+        match number {
+            1 => Some(FormatVersion::V1),
+            2 => Some(FormatVersion::V2),
+            _ => None,
+        }
+    }
+}
 
 /// This trait is implemented by Game and can be used to serialize/deserialize Hex games to/from strings or JSON.
 pub trait Serialization: Sized {
     /// Save this game as a Serde JSON value
-    fn save_to_json(&self) -> serde_json::Value;
+    fn save_to_json(&self) -> serde_json::Value {
+        self.save_to_json_with_version(FormatVersion::LATEST)
+    }
+    /// Save this game as a Serde JSON value using the given format version.
+    /// Use this to support consumers that cannot read the latest version. Information that the version cannot store is lost.
+    fn save_to_json_with_version(&self, version: FormatVersion) -> serde_json::Value;
     /// Save this game to a JSON string.
     fn save_to_string(&self) -> String {
         self.save_to_json().to_string()
     }
     /// Load a game from a Serde JSON value.
-    fn load_from_json(value: Value) -> Result<Self>;
+    ///
+    /// Games stored in version 1 have no move list. They are loaded as games starting from the stored position (see `Game::get_setup`).
+    fn load_from_json(value: Value) -> Result<Self, LoadError>;
     /// Load a game from a JSON string.
-    fn load_from_str(string: &str) -> Result<Self> {
+    fn load_from_str(string: &str) -> Result<Self, LoadError> {
         let value: Value = serde_json::from_str(string)?;
         Self::load_from_json(value)
     }
@@ -31,35 +62,251 @@ struct StoredGame {
     size: CoordValue,
     current_player: u8,
     stones: Vec<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<StoredResult>,
+    // The following fields have been added in version 2
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    moves: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    setup: Option<StoredSetup>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metadata: Option<StoredMetadata>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredResult {
+    winner: u8,
+    reason: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredSetup {
+    current_player: u8,
+    stones: Vec<Vec<u8>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    black: Option<StoredPlayerInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    white: Option<StoredPlayerInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(default)]
+    swap_rule: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredPlayerInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rating: Option<u32>,
 }
 
 impl Serialization for Game {
-    fn save_to_json(&self) -> Value {
-        let stored_game = StoredGame {
-            version: VERSION,
+    fn save_to_json_with_version(&self, version: FormatVersion) -> Value {
+        let mut stored_game = StoredGame {
+            version: version as u8,
             size: self.get_board().size(),
             current_player: serialize_color(&self.get_current_player()),
             stones: store_stone_matrix(&self.get_board().to_stone_matrix()),
+            result: self.get_result().map(store_result),
+            moves: None,
+            setup: None,
+            metadata: None,
         };
 
+        if version == FormatVersion::V2 {
+            stored_game.moves = Some(self.get_moves().iter().map(Move::to_string).collect());
+            stored_game.setup = self.get_setup().map(store_setup);
+            stored_game.metadata = Some(self.get_metadata())
+                .filter(|metadata| !metadata.is_empty())
+                .map(store_metadata);
+        }
+
         serde_json::to_value(&stored_game).expect("Game serialization failed")
     }
 
-    fn load_from_json(value: Value) -> Result<Self> {
+    fn load_from_json(value: Value) -> Result<Self, LoadError> {
         let stored_game: StoredGame = serde_json::from_value(value)?;
 
-        if stored_game.version != VERSION {
-            return Err(invalid_data(format!(
-                "Unsupported version: {}",
-                stored_game.version
-            )));
-        }
+        let version = FormatVersion::from_number(stored_game.version)
+            .ok_or(LoadError::UnsupportedVersion(stored_game.version))?;
 
         let stones = load_stone_matrix(&stored_game.stones)?;
+        if stones.len() != stored_game.size as usize {
+            return Err(LoadError::SizeMismatch(stored_game.size, stones.len()));
+        }
         let current_player = deserialize_color(&stored_game.current_player)?;
+        let result = stored_game.result.as_ref().map(load_result).transpose()?;
+        let metadata = stored_game.metadata.map(load_metadata).unwrap_or_default();
+
+        let mut game = match (version, stored_game.moves) {
+            (FormatVersion::V2, Some(moves)) => {
+                let mut game = match stored_game.setup {
+                    Some(setup) => Game::load(
+                        load_stone_matrix(&setup.stones)?,
+                        deserialize_color(&setup.current_player)?,
+                    )?,
+                    None => Game::new(check_size(stored_game.size)?),
+                };
+                // Metadata must be set before replaying, because it determines whether swapping is allowed.
+                game.set_metadata(metadata);
+                replay_moves(&mut game, &moves)?;
+
+                if game.get_board().to_stone_matrix() != stones {
+                    return Err(LoadError::StonesDoNotMatchMoves);
+                }
+                game
+            }
+            // Version 1 (or version 2 without moves): Start from the stored position.
+            _ => {
+                // Games that did not end by connection cannot be detected from the board alone.
+                let current_player = match result {
+                    Some(result) if result.reason != WinReason::Connection => {
+                        current_player.or(Some(result.loser()))
+                    }
+                    _ => current_player,
+                };
+                let mut game = Game::load(stones, current_player)?;
+                game.set_metadata(metadata);
+                game
+            }
+        };
+
+        match (result, game.get_result()) {
+            (Some(result), Some(actual)) if result != actual => {
+                return Err(LoadError::InvalidResult(format!(
+                    "Stored result \"{}\" does not match the board \"{}\"",
+                    result, actual
+                )));
+            }
+            (Some(result), None) if result.reason == WinReason::Connection => {
+                return Err(LoadError::InvalidResult(format!(
+                    "Stored result \"{}\" but the board has no connection",
+                    result
+                )));
+            }
+            (Some(result), None) => game.finish(result),
+            _ => {}
+        }
+
+        Ok(game)
+    }
+}
+
+fn check_size(size: CoordValue) -> Result<CoordValue, LoadError> {
+    if (crate::MIN_BOARD_SIZE..=crate::MAX_BOARD_SIZE).contains(&size) {
+        Ok(size)
+    } else {
+        Err(LoadError::InvalidBoard(InvalidBoard::SizeOutOfBounds(
+            size as usize,
+            crate::MIN_BOARD_SIZE,
+            crate::MAX_BOARD_SIZE,
+        )))
+    }
+}
 
-        Game::load(stones, current_player).map_err(invalid_data)
+fn replay_moves(game: &mut Game, moves: &[String]) -> Result<(), LoadError> {
+    for (index, string) in moves.iter().enumerate() {
+        let mv: Move = string
+            .parse()
+            .map_err(|_| LoadError::UnparsableMove(index, string.clone()))?;
+        game.play_move(mv)
+            .map_err(|error| LoadError::InvalidMove(index, error))?;
     }
+    Ok(())
+}
+
+fn store_setup(setup: &Setup) -> StoredSetup {
+    StoredSetup {
+        current_player: serialize_color(&setup.current_player),
+        stones: store_stone_matrix(&setup.stones),
+    }
+}
+
+fn store_metadata(metadata: &GameMetadata) -> StoredMetadata {
+    StoredMetadata {
+        black: store_player_info(&metadata.black),
+        white: store_player_info(&metadata.white),
+        event: metadata.event.clone(),
+        date: metadata.date.clone(),
+        swap_rule: metadata.swap_rule,
+        comment: metadata.comment.clone(),
+    }
+}
+
+fn store_player_info(player_info: &PlayerInfo) -> Option<StoredPlayerInfo> {
+    if player_info.name.is_none() && player_info.rating.is_none() {
+        return None;
+    }
+    Some(StoredPlayerInfo {
+        name: player_info.name.clone(),
+        rating: player_info.rating,
+    })
+}
+
+fn load_metadata(stored_metadata: StoredMetadata) -> GameMetadata {
+    GameMetadata {
+        black: stored_metadata
+            .black
+            .map(load_player_info)
+            .unwrap_or_default(),
+        white: stored_metadata
+            .white
+            .map(load_player_info)
+            .unwrap_or_default(),
+        event: stored_metadata.event,
+        date: stored_metadata.date,
+        swap_rule: stored_metadata.swap_rule,
+        comment: stored_metadata.comment,
+    }
+}
+
+fn load_player_info(stored_player_info: StoredPlayerInfo) -> PlayerInfo {
+    PlayerInfo {
+        name: stored_player_info.name,
+        rating: stored_player_info.rating,
+    }
+}
+
+fn store_result(result: GameResult) -> StoredResult {
+    let reason = match result.reason {
+        WinReason::Connection => "connection",
+        WinReason::Resignation => "resignation",
+        WinReason::Timeout => "timeout",
+        WinReason::Forfeit => "forfeit",
+    };
+    StoredResult {
+        winner: serialize_color(&Some(result.winner)),
+        reason: reason.to_string(),
+    }
+}
+
+fn load_result(stored_result: &StoredResult) -> Result<GameResult, LoadError> {
+    let winner = deserialize_color(&stored_result.winner)?
+        .ok_or_else(|| LoadError::InvalidResult("No winner".to_string()))?;
+    let reason = match stored_result.reason.as_str() {
+        "connection" => WinReason::Connection,
+        "resignation" => WinReason::Resignation,
+        "timeout" => WinReason::Timeout,
+        "forfeit" => WinReason::Forfeit,
+        reason => {
+            return Err(LoadError::InvalidResult(format!(
+                "Unknown reason {}",
+                reason
+            )))
+        }
+    };
+    Ok(GameResult::new(winner, reason))
 }
 
 fn store_stone_matrix(stones: &[Vec<Option<Color>>]) -> Vec<Vec<u8>> {
@@ -71,13 +318,28 @@ fn store_row(row: &Vec<Option<Color>>) -> Vec<u8> {
     row.iter().map(serialize_color).collect()
 }
 
-fn load_stone_matrix(stones: &[Vec<u8>]) -> Result<Vec<Vec<Option<Color>>>> {
-    stones.iter().map(load_row).collect()
+fn load_stone_matrix(stones: &[Vec<u8>]) -> Result<Vec<Vec<Option<Color>>>, LoadError> {
+    stones
+        .iter()
+        .enumerate()
+        .map(|(row, stones_in_row)| load_row(row, stones_in_row))
+        .collect()
 }
 
-#[allow(clippy::ptr_arg)]
-fn load_row(row: &Vec<u8>) -> Result<Vec<Option<Color>>> {
-    row.iter().map(deserialize_color).collect()
+fn load_row(row: usize, stones_in_row: &[u8]) -> Result<Vec<Option<Color>>, LoadError> {
+    stones_in_row
+        .iter()
+        .enumerate()
+        .map(|(column, input)| {
+            deserialize_color(input).map_err(|error| match error {
+                LoadError::InvalidColor(code, None) => LoadError::InvalidColor(
+                    code,
+                    Some(Coords::new(row as CoordValue, column as CoordValue)),
+                ),
+                error => error,
+            })
+        })
+        .collect()
 }
 
 fn serialize_color(color: &Option<Color>) -> u8 {
@@ -88,25 +350,23 @@ fn serialize_color(color: &Option<Color>) -> u8 {
     }
 }
 
-fn deserialize_color(input: &u8) -> Result<Option<Color>> {
+fn deserialize_color(input: &u8) -> Result<Option<Color>, LoadError> {
     match input {
         0 => Ok(None),
         1 => Ok(Some(Color::Black)),
         2 => Ok(Some(Color::White)),
-        _ => Err(invalid_data(format!("Invalid color {}", input))),
+        _ => Err(LoadError::InvalidColor(*input, None)),
     }
 }
 
-fn invalid_data<T: ToString>(message: T) -> Error {
-    Error::new(ErrorKind::InvalidData, message.to_string())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::coords::Coords;
+    use crate::errors::InvalidMove;
     use serde_json::json;
 
+    const VERSION: u8 = FormatVersion::LATEST as u8;
+
     #[test]
     fn test_serialize() {
         let mut game = Game::new(2);
@@ -121,11 +381,124 @@ mod tests {
                 "version": VERSION,
                 "size": 2,
                 "currentPlayer": 1,
-                "stones": [[0, 1], [2, 0]]
+                "stones": [[0, 1], [2, 0]],
+                "moves": ["b1", "a2"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_serialize_version_1() {
+        let mut game = Game::new(2);
+        game.play(Coords { row: 0, column: 1 }).unwrap();
+        game.get_metadata_mut().event = Some("Test".to_string());
+
+        let data = game.save_to_json_with_version(FormatVersion::V1);
+
+        assert_eq!(
+            data,
+            json!({
+                "version": 1,
+                "size": 2,
+                "currentPlayer": 2,
+                "stones": [[0, 1], [0, 0]]
             })
         );
     }
 
+    #[test]
+    fn test_deserialize_moves() {
+        let data = json!({
+            "version": VERSION,
+            "size": 3,
+            "currentPlayer": 1,
+            "stones": [[0, 0, 2], [0, 0, 0], [0, 0, 0]],
+            "moves": ["a3", "swap"],
+            "metadata": {"swapRule": true},
+        });
+
+        let game = Game::load_from_json(data).unwrap();
+
+        assert_eq!(
+            game.get_moves(),
+            &[Move::Place(Coords::new(2, 0)), Move::Swap]
+        );
+        assert_eq!(game.get_setup(), None);
+        assert_eq!(game.get_current_player(), Some(Color::Black));
+    }
+
+    #[test]
+    fn test_deserialize_moves_not_matching_stones() {
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 2,
+            "stones": [[0, 1], [0, 0]],
+            "moves": ["a1"],
+        });
+
+        let result = Game::load_from_json(data);
+
+        assert_eq!(result.err().unwrap(), LoadError::StonesDoNotMatchMoves);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_move() {
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 1,
+            "stones": [[1, 0], [0, 0]],
+            "moves": ["a1", "swap"],
+        });
+
+        let error = Game::load_from_json(data).err().unwrap();
+
+        assert_eq!(
+            error,
+            LoadError::InvalidMove(1, InvalidMove::SwapNotAllowed)
+        );
+        assert!(error.to_string().contains("index 1"));
+    }
+
+    #[test]
+    fn test_version_1_is_loaded_without_history() {
+        let data = json!({
+            "version": 1,
+            "size": 2,
+            "currentPlayer": 2,
+            "stones": [[1, 0], [0, 0]],
+        });
+
+        let game = Game::load_from_json(data).unwrap();
+
+        assert_eq!(game.get_moves(), &[]);
+        assert_eq!(
+            game.get_setup().unwrap().stones,
+            vec![vec![Some(Color::Black), None], vec![None, None]]
+        );
+    }
+
+    #[test]
+    fn test_setup_to_string_cycle() {
+        let stone_matrix = vec![
+            vec![None, Some(Color::Black), None],
+            vec![None, None, None],
+            vec![None, None, None],
+        ];
+        let mut game = Game::load(stone_matrix, Some(Color::White)).unwrap();
+        game.play(Coords::new(1, 1)).unwrap();
+
+        let loaded_game = Game::load_from_str(&game.save_to_string()).unwrap();
+
+        assert_eq!(loaded_game.get_setup(), game.get_setup());
+        assert_eq!(loaded_game.get_moves(), game.get_moves());
+        assert_eq!(
+            loaded_game.get_board().to_stone_matrix(),
+            game.get_board().to_stone_matrix()
+        );
+    }
+
     #[test]
     fn test_deserialize() {
         let data = json!({
@@ -192,7 +565,10 @@ mod tests {
 
         let result = Game::load_from_json(data);
 
-        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap(),
+            LoadError::InvalidBoard(InvalidBoard::NoCurrentPlayer)
+        );
     }
 
     #[test]
@@ -206,7 +582,237 @@ mod tests {
 
         let result = Game::load_from_json(data);
 
-        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap(),
+            LoadError::UnsupportedVersion(VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_with_invalid_color() {
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 1,
+            "stones": [[0, 0], [0, 3]],
+        });
+
+        let result = Game::load_from_json(data);
+
+        assert_eq!(
+            result.err().unwrap(),
+            LoadError::InvalidColor(3, Some(Coords::new(1, 1)))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_with_invalid_current_player() {
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 7,
+            "stones": [[0, 0], [0, 0]],
+        });
+
+        let result = Game::load_from_json(data);
+
+        assert_eq!(result.err().unwrap(), LoadError::InvalidColor(7, None));
+    }
+
+    #[test]
+    fn test_deserialize_with_size_mismatch() {
+        let data = json!({
+            "version": VERSION,
+            "size": 3,
+            "currentPlayer": 1,
+            "stones": [[0, 0], [0, 0]],
+        });
+
+        let result = Game::load_from_json(data);
+
+        assert_eq!(result.err().unwrap(), LoadError::SizeMismatch(3, 2));
+    }
+
+    #[test]
+    fn test_deserialize_with_non_square_board() {
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 1,
+            "stones": [[0, 0], [0]],
+        });
+
+        let result = Game::load_from_json(data);
+
+        assert_eq!(
+            result.err().unwrap(),
+            LoadError::InvalidBoard(InvalidBoard::NotSquare(1, 2))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid_json() {
+        let result = Game::load_from_str("{\n\"version\": }");
+
+        match result.err().unwrap() {
+            LoadError::Json(_, line, column) => assert_eq!((line, column), (2, 12)),
+            error => panic!("Unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_serialize_resigned_game() {
+        let mut game = Game::new(2);
+        game.play(Coords { row: 0, column: 0 }).unwrap();
+        game.resign(Color::White).unwrap();
+
+        let data = game.save_to_json();
+
+        assert_eq!(data["currentPlayer"], 0);
+        assert_eq!(
+            data["result"],
+            json!({"winner": 1, "reason": "resignation"})
+        );
+    }
+
+    #[test]
+    fn test_deserialize_resigned_game() {
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 0,
+            "stones": [[1, 0], [0, 0]],
+            "result": {"winner": 1, "reason": "resignation"},
+        });
+
+        let game = Game::load_from_json(data).unwrap();
+
+        assert_eq!(game.get_current_player(), None);
+        assert_eq!(
+            game.get_result(),
+            Some(GameResult::new(Color::Black, WinReason::Resignation))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_with_invalid_result_reason() {
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 0,
+            "stones": [[1, 0], [0, 0]],
+            "result": {"winner": 1, "reason": "boredom"},
+        });
+
+        let result = Game::load_from_json(data);
+
+        assert_eq!(
+            result.err().unwrap(),
+            LoadError::InvalidResult("Unknown reason boredom".to_string())
+        );
+    }
+
+    #[test]
+    fn test_deserialize_result_with_wrong_winner() {
+        // Black has connected top and bottom.
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 0,
+            "stones": [[1, 2], [1, 0]],
+            "result": {"winner": 2, "reason": "connection"},
+        });
+
+        let result = Game::load_from_json(data);
+
+        assert_eq!(
+            result.err().unwrap(),
+            LoadError::InvalidResult(
+                "Stored result \"White wins by connection\" does not match the board \"Black wins by connection\""
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_deserialize_connection_result_without_connection() {
+        let data = json!({
+            "version": VERSION,
+            "size": 2,
+            "currentPlayer": 2,
+            "stones": [[1, 0], [0, 0]],
+            "result": {"winner": 1, "reason": "connection"},
+        });
+
+        let result = Game::load_from_json(data);
+
+        assert_eq!(
+            result.err().unwrap(),
+            LoadError::InvalidResult(
+                "Stored result \"Black wins by connection\" but the board has no connection"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_deserialize_version_1() {
+        let data = json!({
+            "version": 1,
+            "size": 2,
+            "currentPlayer": 2,
+            "stones": [[1, 0], [0, 0]],
+        });
+
+        let game = Game::load_from_json(data).unwrap();
+
+        assert_eq!(game.get_current_player(), Some(Color::White));
+        assert!(game.get_metadata().is_empty());
+    }
+
+    #[test]
+    fn test_serialize_metadata() {
+        let mut game = Game::new(2);
+        let metadata = game.get_metadata_mut();
+        metadata.black.name = Some("Alice".to_string());
+        metadata.white.rating = Some(1850);
+        metadata.date = Some("2021-03-14".to_string());
+        metadata.swap_rule = true;
+
+        let data = game.save_to_json();
+
+        assert_eq!(
+            data["metadata"],
+            json!({
+                "black": {"name": "Alice"},
+                "white": {"rating": 1850},
+                "date": "2021-03-14",
+                "swapRule": true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_metadata_to_string_cycle() {
+        let mut game = Game::new(2);
+        game.set_metadata(GameMetadata {
+            black: PlayerInfo {
+                name: Some("Alice".to_string()),
+                rating: Some(1700),
+            },
+            white: PlayerInfo {
+                name: Some("Bob".to_string()),
+                rating: None,
+            },
+            event: Some("Hex Open".to_string()),
+            date: Some("2021-03-14".to_string()),
+            swap_rule: true,
+            comment: Some("Great game".to_string()),
+        });
+
+        let loaded_game = Game::load_from_str(&game.save_to_string()).unwrap();
+
+        assert_eq!(loaded_game.get_metadata(), game.get_metadata());
     }
 
     #[test]
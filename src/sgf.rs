@@ -0,0 +1,897 @@
+use crate::board::{StoneMatrix, MAX_BOARD_SIZE, MIN_BOARD_SIZE};
+use crate::color::Color;
+use crate::coords::{CoordValue, Coords};
+use crate::errors::{InvalidMove, ParseSgfError};
+use crate::game::{Game, Setup};
+use crate::metadata::GameMetadata;
+use crate::moves::Move;
+use std::fmt::Write;
+
+// HexGui's default board size, used if an SGF file has no SZ property.
+const DEFAULT_SIZE: CoordValue = 11;
+
+/// Identifies a node within a `GameTree`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// A node of a `GameTree`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    mv: Option<Move>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    /// A comment on the position after this node's move.
+    pub comment: Option<String>,
+    /// Labels shown on cells of the board, e.g. "A" to refer to a cell in the comment.
+    pub labels: Vec<(Coords, String)>,
+}
+
+impl Node {
+    fn new(mv: Option<Move>, parent: Option<NodeId>) -> Self {
+        Self {
+            mv,
+            parent,
+            children: vec![],
+            comment: None,
+            labels: vec![],
+        }
+    }
+
+    /// Return the move of this node. The root node and nodes that only carry comments or labels have no move.
+    pub fn get_move(&self) -> Option<Move> {
+        self.mv
+    }
+
+    /// Return the parent of this node, or None for the root node.
+    pub fn get_parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// Return the children of this node. The first child continues the main line, further children are variations.
+    pub fn get_children(&self) -> &[NodeId] {
+        &self.children
+    }
+}
+
+/// A game record with variations, comments and labels, as used by HexGui.
+///
+/// The root node represents the initial position (an empty board or the setup). Each further node contains a move
+/// and the children of a node are alternative continuations. Game trees can be loaded from and saved to HexGui's
+/// SGF dialect (`.sgf`/`.hgf` files) and each node can be replayed into a `Game`.
+///
+/// ```
+/// # use hexgame::{Coords, GameTree, Move};
+/// let mut tree = GameTree::load_sgf("(;GM[11]SZ[5];B[c3](;W[c2]C[Solid])(;W[d2]))").unwrap();
+///
+/// let first_move = tree.get_node(tree.get_root()).get_children()[0];
+/// let variations = tree.get_node(first_move).get_children().to_vec();
+/// assert_eq!(variations.len(), 2);
+/// assert_eq!(tree.get_node(variations[0]).comment.as_deref(), Some("Solid"));
+///
+/// let new_node = tree.add_move(variations[1], Move::Place(Coords::new(1, 1)));
+/// let game = tree.replay(new_node).unwrap();
+/// assert_eq!(game.get_moves().len(), 3);
+///
+/// assert_eq!(tree.to_sgf(), "(;FF[4]GM[11]SZ[5];B[c3](;W[c2]C[Solid])(;W[d2];B[b2]))");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameTree {
+    size: CoordValue,
+    setup: Option<Setup>,
+    metadata: GameMetadata,
+    nodes: Vec<Node>,
+}
+
+impl GameTree {
+    /// Create a game tree that contains only the root node.
+    ///
+    /// This method will panic if the size is not bounded by `MIN_BOARD_SIZE` and `MAX_BOARD_SIZE`.
+    pub fn new(size: CoordValue) -> Self {
+        assert!(
+            (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&size),
+            "Invalid board size"
+        );
+        Self {
+            size,
+            setup: None,
+            metadata: GameMetadata::default(),
+            nodes: vec![Node::new(None, None)],
+        }
+    }
+
+    /// Create a game tree with a single line of play from the moves of a game, including its setup and metadata.
+    pub fn from_game(game: &Game) -> Self {
+        let mut tree = Self::new(game.get_board().size());
+        tree.setup = game.get_setup().cloned();
+        tree.metadata = game.get_metadata().clone();
+        let mut node = tree.get_root();
+        for mv in game.get_moves() {
+            node = tree.add_move(node, *mv);
+        }
+        tree
+    }
+
+    /// Return the size of the board.
+    pub fn get_size(&self) -> CoordValue {
+        self.size
+    }
+
+    /// Return the position at the root node, or None if the game starts on an empty board.
+    pub fn get_setup(&self) -> Option<&Setup> {
+        self.setup.as_ref()
+    }
+
+    /// Return the metadata of the game (player names, event, etc.).
+    pub fn get_metadata(&self) -> &GameMetadata {
+        &self.metadata
+    }
+
+    /// Return the metadata of the game for modification.
+    pub fn get_metadata_mut(&mut self) -> &mut GameMetadata {
+        &mut self.metadata
+    }
+
+    /// Return the root node.
+    pub fn get_root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    /// Return the node with the given id.
+    ///
+    /// This method will panic if the id belongs to another tree.
+    pub fn get_node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+
+    /// Return the node with the given id for modification of its comment and labels.
+    pub fn get_node_mut(&mut self, id: NodeId) -> &mut Node {
+        &mut self.nodes[id.0]
+    }
+
+    /// Add a move as a child of the given node and return the new node.
+    /// If the node already has a child with this move, that child is returned instead.
+    ///
+    /// Moves are not validated until the tree is replayed (see `replay`).
+    pub fn add_move(&mut self, parent: NodeId, mv: Move) -> NodeId {
+        let existing_child = self.nodes[parent.0]
+            .children
+            .iter()
+            .find(|child| self.nodes[child.0].mv == Some(mv));
+        match existing_child {
+            Some(child) => *child,
+            None => self.push_node(parent, Some(mv)),
+        }
+    }
+
+    fn push_node(&mut self, parent: NodeId, mv: Option<Move>) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node::new(mv, Some(parent)));
+        self.nodes[parent.0].children.push(id);
+        id
+    }
+
+    /// Return the nodes of the main line, i.e. the root node followed by the first child of each node.
+    pub fn get_main_line(&self) -> Vec<NodeId> {
+        let mut line = vec![self.get_root()];
+        while let Some(child) = self.get_node(*line.last().unwrap()).children.first() {
+            line.push(*child);
+        }
+        line
+    }
+
+    /// Return the moves leading from the root to the given node.
+    pub fn get_moves_to(&self, id: NodeId) -> Vec<Move> {
+        let mut moves = vec![];
+        let mut node = Some(id);
+        while let Some(id) = node {
+            moves.extend(self.get_node(id).mv);
+            node = self.get_node(id).parent;
+        }
+        moves.reverse();
+        moves
+    }
+
+    /// Return the game at the given node, i.e. the setup (or an empty board) after playing all moves leading to this node.
+    /// Returns an error if one of these moves is invalid.
+    pub fn replay(&self, id: NodeId) -> Result<Game, InvalidMove> {
+        let mut game = match &self.setup {
+            Some(setup) => Game::load(setup.stones.clone(), setup.current_player)
+                .expect("The setup has been validated"),
+            None => Game::new(self.size),
+        };
+        game.set_metadata(self.metadata.clone());
+        for mv in self.get_moves_to(id) {
+            game.play_move(mv)?;
+        }
+        Ok(game)
+    }
+
+    /// Load a game tree from HexGui's SGF dialect.
+    ///
+    /// Moves are written like "B[c4]" or "W[swap-pieces]". Comments (C), labels (LB), setup stones (AB, AW, PL) and
+    /// metadata (PB, PW, BR, WR, EV, DT, GC) are supported, other properties are ignored. Only the first game of a collection is loaded.
+    /// If the tree contains a swap, the swap rule is enabled in the metadata.
+    pub fn load_sgf(string: &str) -> Result<Self, ParseSgfError> {
+        let mut parser = Parser {
+            input: string,
+            position: 0,
+        };
+        let mut tree = Self::new(DEFAULT_SIZE);
+        parser.skip_whitespace();
+        parser.parse_tree(&mut tree)?;
+
+        if tree.nodes.iter().any(|node| node.mv == Some(Move::Swap)) {
+            tree.metadata.swap_rule = true;
+        }
+        Ok(tree)
+    }
+
+    /// Save this game tree in HexGui's SGF dialect (see `load_sgf`).
+    pub fn to_sgf(&self) -> String {
+        let mut sgf = String::new();
+        write!(sgf, "(;FF[4]GM[11]SZ[{}]", self.size).unwrap();
+        self.write_metadata(&mut sgf);
+        self.write_setup(&mut sgf);
+        self.write_annotations(&mut sgf, self.get_root());
+
+        let first_player = self
+            .setup
+            .as_ref()
+            .and_then(|setup| setup.current_player)
+            .unwrap_or(Color::Black);
+        self.write_children(&mut sgf, self.get_root(), first_player);
+        sgf.push(')');
+        sgf
+    }
+
+    fn write_metadata(&self, sgf: &mut String) {
+        let metadata = &self.metadata;
+        let properties = [
+            ("PB", metadata.black.name.clone()),
+            ("BR", metadata.black.rating.map(|rating| rating.to_string())),
+            ("PW", metadata.white.name.clone()),
+            ("WR", metadata.white.rating.map(|rating| rating.to_string())),
+            ("EV", metadata.event.clone()),
+            ("DT", metadata.date.clone()),
+            ("GC", metadata.comment.clone()),
+        ];
+        for (name, value) in properties {
+            if let Some(value) = value {
+                write!(sgf, "{}[{}]", name, escape(&value)).unwrap();
+            }
+        }
+    }
+
+    fn write_setup(&self, sgf: &mut String) {
+        let setup = match &self.setup {
+            Some(setup) => setup,
+            None => return,
+        };
+
+        for (name, color) in [("AB", Color::Black), ("AW", Color::White)] {
+            let cells: Vec<Coords> = stone_coords(&setup.stones, color);
+            if !cells.is_empty() {
+                sgf.push_str(name);
+                for coords in cells {
+                    write!(sgf, "[{}]", coords).unwrap();
+                }
+            }
+        }
+        if let Some(color) = setup.current_player {
+            write!(sgf, "PL[{}]", color_letter(color)).unwrap();
+        }
+    }
+
+    fn write_annotations(&self, sgf: &mut String, id: NodeId) {
+        let node = self.get_node(id);
+        if let Some(comment) = &node.comment {
+            write!(sgf, "C[{}]", escape(comment)).unwrap();
+        }
+        if !node.labels.is_empty() {
+            sgf.push_str("LB");
+            for (coords, label) in &node.labels {
+                write!(sgf, "[{}:{}]", coords, escape(label)).unwrap();
+            }
+        }
+    }
+
+    /// Write the subtrees below the given node. `player` is the player who makes the moves of the children.
+    ///
+    /// This uses an explicit stack instead of recursion, so that deep trees cannot overflow the call stack.
+    fn write_children(&self, sgf: &mut String, id: NodeId, player: Color) {
+        let mut stack = vec![WriteTask::Children(id, player)];
+
+        while let Some(task) = stack.pop() {
+            match task {
+                WriteTask::Children(id, player) => {
+                    let children = &self.get_node(id).children;
+                    if let [child] = children.as_slice() {
+                        stack.push(WriteTask::Node(*child, player));
+                    } else {
+                        for child in children.iter().rev() {
+                            stack.push(WriteTask::Text(')'));
+                            stack.push(WriteTask::Node(*child, player));
+                            stack.push(WriteTask::Text('('));
+                        }
+                    }
+                }
+                WriteTask::Node(id, player) => {
+                    let next_player = self.write_node(sgf, id, player);
+                    stack.push(WriteTask::Children(id, next_player));
+                }
+                WriteTask::Text(c) => sgf.push(c),
+            }
+        }
+    }
+
+    /// Write a single node (without its children) and return the player who moves next.
+    fn write_node(&self, sgf: &mut String, id: NodeId, player: Color) -> Color {
+        sgf.push(';');
+        let next_player = match self.get_node(id).mv {
+            Some(mv) => {
+                let value = match mv {
+                    Move::Place(coords) => coords.to_string(),
+                    Move::Swap => "swap-pieces".to_string(),
+                };
+                write!(sgf, "{}[{}]", color_letter(player), value).unwrap();
+                // After swapping, Black moves next as after any other move by White.
+                player.opponent_color()
+            }
+            None => player,
+        };
+        self.write_annotations(sgf, id);
+        next_player
+    }
+}
+
+enum WriteTask {
+    Children(NodeId, Color),
+    Node(NodeId, Color),
+    Text(char),
+}
+
+fn stone_coords(stones: &StoneMatrix, color: Color) -> Vec<Coords> {
+    let mut cells = vec![];
+    for (row, row_stones) in stones.iter().enumerate() {
+        for (column, stone) in row_stones.iter().enumerate() {
+            if *stone == Some(color) {
+                cells.push(Coords::new(row as CoordValue, column as CoordValue));
+            }
+        }
+    }
+    cells
+}
+
+fn color_letter(color: Color) -> char {
+    match color {
+        Color::Black => 'B',
+        Color::White => 'W',
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+struct Property {
+    name: String,
+    // Values with their byte positions
+    values: Vec<(usize, String)>,
+}
+
+impl Property {
+    fn invalid_value(&self, position: usize, value: &str) -> ParseSgfError {
+        ParseSgfError::InvalidValue(position, self.name.clone(), value.to_string())
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn syntax_error(&self, message: &str) -> ParseSgfError {
+        ParseSgfError::Syntax(self.position, message.to_string())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseSgfError> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.syntax_error(&format!("Expected '{}'", expected)))
+        }
+    }
+
+    /// Parse GameTree = "(" Node+ GameTree* ")". The first node is applied to the root of `tree`.
+    ///
+    /// Nested game trees are handled with an explicit stack instead of recursion, so that deeply nested input cannot
+    /// overflow the call stack.
+    fn parse_tree(&mut self, tree: &mut GameTree) -> Result<(), ParseSgfError> {
+        // The last node of each game tree that has been opened, but not closed yet
+        let mut open_trees: Vec<NodeId> = vec![];
+
+        loop {
+            let parent = open_trees.last().copied();
+            let last_node = self.parse_sequence(tree, parent)?;
+            open_trees.push(last_node);
+
+            loop {
+                match self.peek() {
+                    Some('(') => break,
+                    Some(')') => {
+                        self.position += 1;
+                        open_trees.pop();
+                        if open_trees.is_empty() {
+                            return Ok(());
+                        }
+                        self.skip_whitespace();
+                    }
+                    Some(_) => return Err(self.syntax_error("Expected '(' or ')'")),
+                    None => return Err(self.syntax_error("Unexpected end of input")),
+                }
+            }
+        }
+    }
+
+    /// Parse "(" Node+. The nodes are added below `parent`, or to the root node if `parent` is None.
+    /// Return the last node.
+    fn parse_sequence(
+        &mut self,
+        tree: &mut GameTree,
+        parent: Option<NodeId>,
+    ) -> Result<NodeId, ParseSgfError> {
+        self.expect('(')?;
+        self.skip_whitespace();
+
+        let mut current = parent;
+        while self.peek() == Some(';') {
+            self.position += 1;
+            let properties = self.parse_properties()?;
+            current = Some(match current {
+                None => {
+                    apply_root_properties(tree, &properties)?;
+                    tree.get_root()
+                }
+                Some(parent) => apply_node_properties(tree, parent, &properties)?,
+            });
+        }
+
+        match current {
+            Some(current) if Some(current) != parent => Ok(current),
+            _ => Err(self.syntax_error("Expected ';'")),
+        }
+    }
+
+    fn parse_properties(&mut self) -> Result<Vec<Property>, ParseSgfError> {
+        let mut properties = vec![];
+        self.skip_whitespace();
+
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_uppercase()) {
+            let start = self.position;
+            let mut c = c;
+            while c.is_ascii_uppercase() {
+                self.position += 1;
+                c = self.peek().unwrap_or(' ');
+            }
+            let name = self.input[start..self.position].to_string();
+
+            let mut values = vec![];
+            self.skip_whitespace();
+            while self.peek() == Some('[') {
+                values.push(self.parse_value()?);
+                self.skip_whitespace();
+            }
+            if values.is_empty() {
+                return Err(self.syntax_error("Expected '['"));
+            }
+            properties.push(Property { name, values });
+        }
+
+        Ok(properties)
+    }
+
+    fn parse_value(&mut self) -> Result<(usize, String), ParseSgfError> {
+        self.expect('[')?;
+        let start = self.position;
+        let mut value = String::new();
+        let mut chars = self.input[self.position..].char_indices();
+
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                ']' => {
+                    self.position += offset + 1;
+                    return Ok((start, value));
+                }
+                '\\' => {
+                    if let Some((_, escaped)) = chars.next() {
+                        value.push(escaped);
+                    }
+                }
+                _ => value.push(c),
+            }
+        }
+
+        self.position = self.input.len();
+        Err(self.syntax_error("Unexpected end of input"))
+    }
+}
+
+fn apply_root_properties(
+    tree: &mut GameTree,
+    properties: &[Property],
+) -> Result<(), ParseSgfError> {
+    let mut black_stones = vec![];
+    let mut white_stones = vec![];
+    let mut current_player = None;
+
+    for property in properties {
+        let (position, value) = &property.values[0];
+        let value = value.as_str();
+        let metadata = &mut tree.metadata;
+        match property.name.as_str() {
+            "GM" if value != "11" => {
+                return Err(ParseSgfError::UnsupportedGame(value.to_string()));
+            }
+            "SZ" => {
+                tree.size = value
+                    .parse()
+                    .ok()
+                    .filter(|size| (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(size))
+                    .ok_or_else(|| property.invalid_value(*position, value))?;
+            }
+            "PB" => metadata.black.name = Some(value.to_string()),
+            "PW" => metadata.white.name = Some(value.to_string()),
+            "BR" => metadata.black.rating = Some(parse_rating(property)?),
+            "WR" => metadata.white.rating = Some(parse_rating(property)?),
+            "EV" => metadata.event = Some(value.to_string()),
+            "DT" => metadata.date = Some(value.to_string()),
+            "GC" => metadata.comment = Some(value.to_string()),
+            "AB" => black_stones.extend(parse_coords_list(property)?),
+            "AW" => white_stones.extend(parse_coords_list(property)?),
+            "PL" => current_player = Some(parse_color(property)?),
+            "B" | "W" => {
+                // Moves in the root node are not supported.
+                return Err(property.invalid_value(*position, value));
+            }
+            _ => {}
+        }
+    }
+    apply_annotations(tree, tree.get_root(), properties)?;
+
+    if !black_stones.is_empty() || !white_stones.is_empty() || current_player.is_some() {
+        let size = tree.size as usize;
+        let mut stones: StoneMatrix = vec![vec![None; size]; size];
+        for (color, cells) in [(Color::Black, black_stones), (Color::White, white_stones)] {
+            for (position, coords) in cells {
+                if !coords.is_on_board_with_size(tree.size) {
+                    return Err(ParseSgfError::InvalidValue(
+                        position,
+                        color_setup_property(color).to_string(),
+                        coords.to_string(),
+                    ));
+                }
+                stones[coords.row as usize][coords.column as usize] = Some(color);
+            }
+        }
+        // Like HexGui, assume that Black moves next if PL is missing.
+        let current_player = current_player.or(Some(Color::Black));
+        let game = Game::load(stones, current_player).expect(
+            "The stones form a square board with a valid size and the current player is known",
+        );
+        tree.setup = game.get_setup().cloned();
+    }
+
+    Ok(())
+}
+
+fn color_setup_property(color: Color) -> &'static str {
+    match color {
+        Color::Black => "AB",
+        Color::White => "AW",
+    }
+}
+
+fn apply_node_properties(
+    tree: &mut GameTree,
+    parent: NodeId,
+    properties: &[Property],
+) -> Result<NodeId, ParseSgfError> {
+    let mut mv = None;
+    for property in properties {
+        if property.name == "B" || property.name == "W" {
+            let (position, value) = &property.values[0];
+            mv = Some(match value.as_str() {
+                "swap-pieces" | "swap-sides" => Move::Swap,
+                _ => Move::Place(
+                    value
+                        .parse()
+                        .map_err(|_| property.invalid_value(*position, value))?,
+                ),
+            });
+        }
+    }
+
+    let id = tree.push_node(parent, mv);
+    apply_annotations(tree, id, properties)?;
+    Ok(id)
+}
+
+fn apply_annotations(
+    tree: &mut GameTree,
+    id: NodeId,
+    properties: &[Property],
+) -> Result<(), ParseSgfError> {
+    for property in properties {
+        match property.name.as_str() {
+            "C" => tree.get_node_mut(id).comment = Some(property.values[0].1.clone()),
+            "LB" => {
+                for (position, value) in &property.values {
+                    let label = value
+                        .split_once(':')
+                        .and_then(|(coords, text)| Some((coords.parse().ok()?, text.to_string())))
+                        .ok_or_else(|| property.invalid_value(*position, value))?;
+                    tree.get_node_mut(id).labels.push(label);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn parse_coords_list(property: &Property) -> Result<Vec<(usize, Coords)>, ParseSgfError> {
+    property
+        .values
+        .iter()
+        .map(|(position, value)| {
+            value
+                .parse()
+                .map(|coords| (*position, coords))
+                .map_err(|_| property.invalid_value(*position, value))
+        })
+        .collect()
+}
+
+fn parse_rating(property: &Property) -> Result<u32, ParseSgfError> {
+    let (position, value) = &property.values[0];
+    value
+        .parse()
+        .map_err(|_| property.invalid_value(*position, value))
+}
+
+fn parse_color(property: &Property) -> Result<Color, ParseSgfError> {
+    let (position, value) = &property.values[0];
+    match value.as_str() {
+        "B" => Ok(Color::Black),
+        "W" => Ok(Color::White),
+        _ => Err(property.invalid_value(*position, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Shortened version of a file written by HexGui
+    const HEXGUI_FILE: &str = "(;AP[HexGui:0.9.GIT]FF[4]GM[11]SZ[9]PB[Alice]PW[Bob]
+        ;B[e5]C[Center opening]
+        ;W[swap-pieces]
+        ;B[c7]
+        (;W[d6]LB[c6:A][b7:B])
+        (;W[c6]C[Alternative]))";
+
+    #[test]
+    fn test_load_hexgui_file() {
+        let tree = GameTree::load_sgf(HEXGUI_FILE).unwrap();
+
+        assert_eq!(tree.get_size(), 9);
+        assert_eq!(tree.get_metadata().black.name.as_deref(), Some("Alice"));
+        assert_eq!(tree.get_metadata().white.name.as_deref(), Some("Bob"));
+        assert!(tree.get_metadata().swap_rule);
+
+        let main_line = tree.get_main_line();
+        assert_eq!(main_line.len(), 5);
+        assert_eq!(
+            tree.get_moves_to(main_line[4]),
+            vec![
+                Move::Place(Coords::new(4, 4)),
+                Move::Swap,
+                Move::Place(Coords::new(6, 2)),
+                Move::Place(Coords::new(5, 3)),
+            ]
+        );
+        assert_eq!(
+            tree.get_node(main_line[1]).comment.as_deref(),
+            Some("Center opening")
+        );
+        assert_eq!(
+            tree.get_node(main_line[4]).labels,
+            vec![
+                (Coords::new(5, 2), "A".to_string()),
+                (Coords::new(6, 1), "B".to_string())
+            ]
+        );
+
+        let variations = tree.get_node(main_line[3]).get_children();
+        assert_eq!(variations.len(), 2);
+        assert_eq!(
+            tree.get_node(variations[1]).get_move(),
+            Some(Move::Place(Coords::new(5, 2)))
+        );
+        assert_eq!(
+            tree.get_node(variations[1]).get_parent(),
+            Some(main_line[3])
+        );
+    }
+
+    #[test]
+    fn test_replay() {
+        let tree = GameTree::load_sgf(HEXGUI_FILE).unwrap();
+        let node = tree.get_main_line()[3];
+
+        let game = tree.replay(node).unwrap();
+
+        assert_eq!(game.get_metadata().black.name.as_deref(), Some("Alice"));
+        assert_eq!(
+            game.get_board().get_color(Coords::new(4, 4)),
+            Some(Color::White)
+        );
+        assert_eq!(
+            game.get_board().get_color(Coords::new(6, 2)),
+            Some(Color::Black)
+        );
+        assert_eq!(game.get_current_player(), Some(Color::White));
+    }
+
+    #[test]
+    fn test_replay_invalid_move() {
+        let tree = GameTree::load_sgf("(;SZ[3];B[a1];W[a1])").unwrap();
+        let node = tree.get_main_line()[2];
+
+        assert_eq!(
+            tree.replay(node).err().unwrap(),
+            InvalidMove::CellOccupied(Coords::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn test_sgf_cycle() {
+        let tree = GameTree::load_sgf(HEXGUI_FILE).unwrap();
+        let sgf = tree.to_sgf();
+
+        assert_eq!(
+            sgf,
+            "(;FF[4]GM[11]SZ[9]PB[Alice]PW[Bob];B[e5]C[Center opening];W[swap-pieces];B[c7](;W[d6]LB[c6:A][b7:B])(;W[c6]C[Alternative]))"
+        );
+        assert_eq!(GameTree::load_sgf(&sgf).unwrap(), tree);
+    }
+
+    #[test]
+    fn test_escaping() {
+        let mut tree = GameTree::new(5);
+        tree.get_metadata_mut().event = Some("Cup [final]".to_string());
+        let node = tree.add_move(tree.get_root(), Move::Place(Coords::new(0, 0)));
+        tree.get_node_mut(node).comment = Some("a\\b]".to_string());
+
+        let sgf = tree.to_sgf();
+
+        assert_eq!(
+            sgf,
+            "(;FF[4]GM[11]SZ[5]EV[Cup [final\\]];B[a1]C[a\\\\b\\]])"
+        );
+        assert_eq!(GameTree::load_sgf(&sgf).unwrap(), tree);
+    }
+
+    #[test]
+    fn test_setup() {
+        let tree = GameTree::load_sgf("(;SZ[3]AB[a1][b2]AW[c3]PL[W];W[c1])").unwrap();
+
+        let setup = tree.get_setup().unwrap();
+        assert_eq!(setup.stones[1][1], Some(Color::Black));
+        assert_eq!(setup.current_player, Some(Color::White));
+        let game = tree.replay(tree.get_main_line()[1]).unwrap();
+        assert_eq!(
+            game.get_board().get_color(Coords::new(0, 2)),
+            Some(Color::White)
+        );
+
+        assert_eq!(
+            tree.to_sgf(),
+            "(;FF[4]GM[11]SZ[3]AB[a1][b2]AW[c3]PL[W];W[c1])"
+        );
+    }
+
+    #[test]
+    fn test_from_game() {
+        let mut game = Game::new(4);
+        game.get_metadata_mut().date = Some("2021-03-14".to_string());
+        game.play(Coords::new(1, 1)).unwrap();
+        game.play(Coords::new(2, 2)).unwrap();
+
+        let tree = GameTree::from_game(&game);
+
+        assert_eq!(
+            tree.to_sgf(),
+            "(;FF[4]GM[11]SZ[4]DT[2021-03-14];B[b2];W[c3])"
+        );
+    }
+
+    #[test]
+    fn test_add_existing_move() {
+        let mut tree = GameTree::new(5);
+        let root = tree.get_root();
+        let first = tree.add_move(root, Move::Place(Coords::new(2, 2)));
+        let second = tree.add_move(root, Move::Place(Coords::new(2, 2)));
+
+        assert_eq!(first, second);
+        assert_eq!(tree.get_node(root).get_children().len(), 1);
+    }
+
+    #[test]
+    fn test_deeply_nested_variations() {
+        let depth = 100_000;
+        let sgf = format!(
+            "(;FF[4]GM[11]SZ[5]{}{})",
+            "(;C[x]".repeat(depth),
+            ")".repeat(depth)
+        );
+
+        let tree = GameTree::load_sgf(&sgf).unwrap();
+        assert_eq!(tree.nodes.len(), depth + 1);
+        // Single variations are written as a sequence.
+        assert_eq!(
+            tree.to_sgf(),
+            format!("(;FF[4]GM[11]SZ[5]{})", ";C[x]".repeat(depth))
+        );
+
+        let unclosed = "(;GM[11]".to_string() + &"(;".repeat(depth);
+        assert_eq!(
+            GameTree::load_sgf(&unclosed).err().unwrap(),
+            ParseSgfError::Syntax(unclosed.len(), "Unexpected end of input".to_string())
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            GameTree::load_sgf("(;GM[1])").err().unwrap(),
+            ParseSgfError::UnsupportedGame("1".to_string())
+        );
+        assert_eq!(
+            GameTree::load_sgf("(;SZ[30])").err().unwrap(),
+            ParseSgfError::InvalidValue(5, "SZ".to_string(), "30".to_string())
+        );
+        assert_eq!(
+            GameTree::load_sgf("(;SZ[5];B[z])").err().unwrap(),
+            ParseSgfError::InvalidValue(10, "B".to_string(), "z".to_string())
+        );
+        assert_eq!(
+            GameTree::load_sgf("(;SZ[5];B[a1]").err().unwrap(),
+            ParseSgfError::Syntax(13, "Unexpected end of input".to_string())
+        );
+        assert_eq!(
+            GameTree::load_sgf("(;SZ[5];B[a1").err().unwrap(),
+            ParseSgfError::Syntax(12, "Unexpected end of input".to_string())
+        );
+        assert_eq!(
+            GameTree::load_sgf("()").err().unwrap(),
+            ParseSgfError::Syntax(1, "Expected ';'".to_string())
+        );
+        assert_eq!(
+            GameTree::load_sgf("(;SZ[2]AB[c3])").err().unwrap(),
+            ParseSgfError::InvalidValue(10, "AB".to_string(), "c3".to_string())
+        );
+    }
+}
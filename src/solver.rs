@@ -0,0 +1,450 @@
+use crate::board::{Board, MIN_BOARD_SIZE};
+use crate::color::Color;
+use crate::coords::{CoordValue, Coords};
+use crate::edges::get_edges_of_color;
+use crate::errors::{InvalidBoard, LoadError};
+use crate::symmetry::Symmetry;
+use std::sync::OnceLock;
+
+/// Largest board size supported by `SolutionTable`. Tables need one bit per 3^(size*size) positions,
+/// i.e. about 5 MB for 4x4 (5x5 would need about 100 GB).
+pub const MAX_SOLVED_SIZE: CoordValue = 4;
+
+/// The game-theoretic value of a position, see `Board::solve`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+    /// The player who wins with perfect play.
+    pub winner: Color,
+    /// All moves of the player to move that keep (or achieve) a won position. Empty if the player to move loses.
+    pub winning_moves: Vec<Coords>,
+}
+
+/// A table containing the game-theoretic value of every position on boards of one size (up to `MAX_SOLVED_SIZE`).
+///
+/// The table is built by retrograde analysis over all positions in which Black has placed as many stones as White
+/// or one more. Since stones never break a connection, the outcome with perfect play does not change if the game
+/// continues after a connection until the board is full. So only full boards need to be checked for a connection and
+/// all other positions follow from their successors.
+///
+/// Each position is indexed by its cells in base 3 (empty 0, black 1, white 2, row by row with the first cell as least
+/// significant digit) and the table stores one bit per index: whether the player to move wins.
+/// Tables can be stored with `to_bytes` (one byte for the size followed by the bits) and loaded with `from_bytes`.
+///
+/// ```
+/// # use hexgame::{Color, Coords, SolutionTable};
+/// let table = SolutionTable::generate(3);
+/// let solution = table.solve(&hexgame::Board::new(3), Color::Black).unwrap();
+///
+/// assert_eq!(solution.winner, Color::Black);
+/// assert!(solution.winning_moves.contains(&Coords::new(1, 1)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolutionTable {
+    size: CoordValue,
+    bits: Vec<u64>,
+}
+
+impl SolutionTable {
+    /// Solve all positions of the given size. This takes a few seconds for 4x4 in release mode.
+    ///
+    /// Panics if the size is not bounded by `MIN_BOARD_SIZE` and `MAX_SOLVED_SIZE`.
+    pub fn generate(size: CoordValue) -> Self {
+        assert!(
+            (MIN_BOARD_SIZE..=MAX_SOLVED_SIZE).contains(&size),
+            "Only boards up to {}x{} can be solved",
+            MAX_SOLVED_SIZE,
+            MAX_SOLVED_SIZE
+        );
+        let cell_count = size as usize * size as usize;
+        let powers: Vec<usize> = (0..cell_count).map(|i| 3usize.pow(i as u32)).collect();
+        let position_count = 3usize.pow(cell_count as u32);
+        let mut table = Self {
+            size,
+            bits: vec![0; position_count.div_ceil(64)],
+        };
+
+        // Adding a stone increases the index, so iterating backwards visits all successors of a position first.
+        let mut digits = vec![0u8; cell_count];
+        for index in (0..position_count).rev() {
+            decode_digits(index, &mut digits);
+            let black = digits.iter().filter(|digit| **digit == 1).count();
+            let white = digits.iter().filter(|digit| **digit == 2).count();
+            let player = if black == white {
+                Color::Black
+            } else if black == white + 1 {
+                Color::White
+            } else {
+                continue;
+            };
+
+            let wins = if black + white == cell_count {
+                let board = board_from_digits(size, &digits);
+                let [edge, opposite_edge] = get_edges_of_color(player);
+                board.is_in_same_set(edge, opposite_edge)
+            } else {
+                let stone = color_digit(player) as usize;
+                (0..cell_count)
+                    .any(|cell| digits[cell] == 0 && !table.get_bit(index + stone * powers[cell]))
+            };
+            if wins {
+                table.set_bit(index);
+            }
+        }
+
+        table
+    }
+
+    /// Use this table in `Board::solve` and `Game::solve` for boards of its size. Tables up to 3x3 are generated on
+    /// first use otherwise, but a table for 4x4 must be installed before positions of that size can be solved.
+    /// Returns the table as error if a table of this size is already in use.
+    ///
+    /// ```no_run
+    /// # use hexgame::SolutionTable;
+    /// // Written by `hexgame solve 4 table4.bin`
+    /// let bytes = std::fs::read("table4.bin").unwrap();
+    /// SolutionTable::from_bytes(&bytes).unwrap().install().unwrap();
+    /// ```
+    pub fn install(self) -> Result<(), SolutionTable> {
+        TABLES[(self.size - MIN_BOARD_SIZE) as usize].set(self)
+    }
+
+    /// Return the board size of this table.
+    pub fn size(&self) -> CoordValue {
+        self.size
+    }
+
+    /// Return the value of the given position with `player` to move, or None if the board has a different size or
+    /// the numbers of stones cannot occur in a game with this player to move (with or without swap).
+    pub fn solve(&self, board: &Board, player: Color) -> Option<Solution> {
+        if board.size() != self.size {
+            return None;
+        }
+        let cells = board.to_stone_matrix().concat();
+        let black = cells.iter().filter(|c| **c == Some(Color::Black)).count();
+        let white = cells.iter().filter(|c| **c == Some(Color::White)).count();
+
+        // After a swap, White has one stone more than Black. Such positions are transposed with swapped colors.
+        let symmetry = match (player, black as isize - white as isize) {
+            (Color::Black, 0) | (Color::White, 1) => Symmetry::Identity,
+            (Color::White, 0) | (Color::Black, -1) => Symmetry::TransposeSwapColors,
+            _ => return None,
+        };
+        let board = symmetry.apply_to_board(board);
+        let player = symmetry.apply_to_color(player);
+
+        let cell_index =
+            |coords: Coords| coords.row as usize * self.size as usize + coords.column as usize;
+        let mut index = 0;
+        for coords in all_coords(self.size) {
+            if let Some(color) = board.get_color(coords) {
+                index += color_digit(color) as usize * 3usize.pow(cell_index(coords) as u32);
+            }
+        }
+
+        let stone = color_digit(player) as usize;
+        let winning_moves: Vec<Coords> = board
+            .get_empty_cells()
+            .into_iter()
+            .filter(|coords| !self.get_bit(index + stone * 3usize.pow(cell_index(*coords) as u32)))
+            .map(|coords| symmetry.apply_to_coords(coords, self.size))
+            .collect();
+        let winner = if self.get_bit(index) {
+            player
+        } else {
+            player.opponent_color()
+        };
+
+        Some(Solution {
+            winner: symmetry.apply_to_color(winner),
+            winning_moves,
+        })
+    }
+
+    /// Encode this table: One byte for the size, followed by the bits (8 per byte, least significant bit first).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.size];
+        bytes.extend(self.bits.iter().flat_map(|word| word.to_le_bytes()));
+        bytes.truncate(1 + byte_count(self.size));
+        bytes
+    }
+
+    /// Decode a table from the format produced by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LoadError> {
+        let size = *bytes.first().ok_or(LoadError::InvalidLength(1, 0))?;
+        if !(MIN_BOARD_SIZE..=MAX_SOLVED_SIZE).contains(&size) {
+            return Err(LoadError::InvalidBoard(InvalidBoard::SizeOutOfBounds(
+                size as usize,
+                MIN_BOARD_SIZE,
+                MAX_SOLVED_SIZE,
+            )));
+        }
+        let expected = 1 + byte_count(size);
+        if bytes.len() != expected {
+            return Err(LoadError::InvalidLength(expected, bytes.len()));
+        }
+
+        let bits = bytes[1..]
+            .chunks(8)
+            .map(|chunk| {
+                let mut word = [0u8; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(word)
+            })
+            .collect();
+        Ok(Self { size, bits })
+    }
+
+    fn get_bit(&self, index: usize) -> bool {
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn set_bit(&mut self, index: usize) {
+        self.bits[index / 64] |= 1 << (index % 64);
+    }
+}
+
+fn byte_count(size: CoordValue) -> usize {
+    3usize.pow(size as u32 * size as u32).div_ceil(8)
+}
+
+fn color_digit(color: Color) -> u8 {
+    match color {
+        Color::Black => 1,
+        Color::White => 2,
+    }
+}
+
+fn decode_digits(mut index: usize, digits: &mut [u8]) {
+    for digit in digits.iter_mut() {
+        *digit = (index % 3) as u8;
+        index /= 3;
+    }
+}
+
+fn all_coords(size: CoordValue) -> impl Iterator<Item = Coords> {
+    (0..size).flat_map(move |row| (0..size).map(move |column| Coords { row, column }))
+}
+
+fn board_from_digits(size: CoordValue, digits: &[u8]) -> Board {
+    let mut board = Board::new(size);
+    for (coords, digit) in all_coords(size).zip(digits) {
+        let color = match digit {
+            1 => Color::Black,
+            2 => Color::White,
+            _ => continue,
+        };
+        board.play(coords, color).expect("Each cell is played once");
+    }
+    board
+}
+
+/// The tables used by `solve`, indexed by size - `MIN_BOARD_SIZE`.
+static TABLES: [OnceLock<SolutionTable>; MAX_SOLVED_SIZE as usize - 1] =
+    [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+
+/// Largest size for which `solve` generates a missing table on first use. Larger tables take too long to generate
+/// without notice and must be installed with `SolutionTable::install`.
+const MAX_GENERATED_SIZE: CoordValue = 3;
+
+/// Solve the position with the table for its size. Tables up to `MAX_GENERATED_SIZE` are generated on first use
+/// unless one has been installed.
+pub fn solve(board: &Board, player: Color) -> Option<Solution> {
+    let size = board.size();
+    if size > MAX_SOLVED_SIZE {
+        return None;
+    }
+    let table = &TABLES[(size - MIN_BOARD_SIZE) as usize];
+    let table = if size <= MAX_GENERATED_SIZE {
+        table.get_or_init(|| SolutionTable::generate(size))
+    } else {
+        table.get()?
+    };
+    table.solve(board, player)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Random;
+
+    /// Solve by plain minimax without any table.
+    fn minimax(board: &Board, player: Color) -> bool {
+        let [edge, opposite_edge] = get_edges_of_color(player.opponent_color());
+        if board.is_in_same_set(edge, opposite_edge) {
+            return false;
+        }
+        board.get_empty_cells().into_iter().any(|coords| {
+            let mut board = board.clone();
+            board.play(coords, player).unwrap();
+            let [edge, opposite_edge] = get_edges_of_color(player);
+            board.is_in_same_set(edge, opposite_edge) || !minimax(&board, player.opponent_color())
+        })
+    }
+
+    #[test]
+    fn test_first_player_wins() {
+        for size in 2..=3 {
+            let table = SolutionTable::generate(size);
+            let solution = table.solve(&Board::new(size), Color::Black).unwrap();
+            assert_eq!(solution.winner, Color::Black);
+        }
+    }
+
+    #[test]
+    fn test_winning_moves_on_3x3() {
+        let table = SolutionTable::generate(3);
+        let solution = table.solve(&Board::new(3), Color::Black).unwrap();
+
+        // The short diagonal and the middle row win on 3x3.
+        assert_eq!(
+            solution.winning_moves,
+            vec![
+                Coords::new(0, 2),
+                Coords::new(1, 0),
+                Coords::new(1, 1),
+                Coords::new(1, 2),
+                Coords::new(2, 0)
+            ]
+        );
+    }
+
+    fn assert_matches_minimax(table: &SolutionTable, board: &Board, player: Color) {
+        let solution = table.solve(board, player).unwrap();
+
+        assert_eq!(solution.winner == player, minimax(board, player));
+        for coords in board.get_empty_cells() {
+            let mut child = board.clone();
+            child.play(coords, player).unwrap();
+            let [edge, opposite_edge] = get_edges_of_color(player);
+            let wins = child.is_in_same_set(edge, opposite_edge)
+                || !minimax(&child, player.opponent_color());
+            assert_eq!(solution.winning_moves.contains(&coords), wins, "{}", coords);
+        }
+    }
+
+    #[test]
+    fn test_matches_minimax() {
+        let table = SolutionTable::generate(3);
+        assert_matches_minimax(&table, &Board::new(3), Color::Black);
+
+        let mut board = Board::new(3);
+        board.play(Coords::new(0, 0), Color::Black).unwrap();
+        assert_matches_minimax(&table, &board, Color::White);
+        board.play(Coords::new(1, 1), Color::White).unwrap();
+        assert_matches_minimax(&table, &board, Color::Black);
+    }
+
+    #[test]
+    #[ignore = "generating the 4x4 table takes more than a minute in debug mode"]
+    fn test_4x4() {
+        let table = SolutionTable::generate(4);
+        let solution = table.solve(&Board::new(4), Color::Black).unwrap();
+        assert_eq!(solution.winner, Color::Black);
+
+        // Random positions with 8 or 9 empty cells are small enough for minimax.
+        let mut random = Random::new(4);
+        for stone_count in (7..=8).cycle().take(20) {
+            let mut cells: Vec<Coords> = all_coords(4).collect();
+            random.shuffle(&mut cells);
+            let mut board = Board::new(4);
+            let mut player = Color::Black;
+            for coords in &cells[..stone_count] {
+                board.play(*coords, player).unwrap();
+                player = player.opponent_color();
+            }
+            assert_matches_minimax(&table, &board, player);
+        }
+    }
+
+    #[test]
+    fn test_finished_position() {
+        let board: Board = "
+            .  ●  .
+            .  ●  .
+            ○  ●  ○
+        "
+        .parse()
+        .unwrap();
+
+        let solution = SolutionTable::generate(3)
+            .solve(&board, Color::White)
+            .unwrap();
+
+        assert_eq!(solution.winner, Color::Black);
+        assert!(solution.winning_moves.is_empty());
+    }
+
+    #[test]
+    fn test_after_swap() {
+        // After a swap, White has a stone on a3 (the mirrored c1) and Black is to move.
+        let mut board = Board::new(3);
+        board.play(Coords::new(2, 0), Color::White).unwrap();
+        let solution = solve(&board, Color::Black).unwrap();
+
+        // The equivalent position: Black has played c1 and White is to move.
+        let mut equivalent = Board::new(3);
+        equivalent.play(Coords::new(0, 2), Color::Black).unwrap();
+        let equivalent_solution = solve(&equivalent, Color::White).unwrap();
+
+        assert_eq!(solution.winner, equivalent_solution.winner.opponent_color());
+        let mapped_moves: Vec<Coords> = equivalent_solution
+            .winning_moves
+            .iter()
+            .map(|coords| Symmetry::TransposeSwapColors.apply_to_coords(*coords, 3))
+            .collect();
+        assert_eq!(solution.winning_moves, mapped_moves);
+    }
+
+    #[test]
+    fn test_invalid_positions() {
+        let mut board = Board::new(3);
+        board.play(Coords::new(0, 0), Color::Black).unwrap();
+        board.play(Coords::new(0, 1), Color::Black).unwrap();
+
+        assert_eq!(solve(&board, Color::Black), None);
+        assert_eq!(solve(&Board::new(5), Color::Black), None);
+        assert_eq!(SolutionTable::generate(2).solve(&board, Color::White), None);
+    }
+
+    #[test]
+    fn test_4x4_requires_installed_table() {
+        // No test installs a table for 4x4.
+        assert_eq!(solve(&Board::new(4), Color::Black), None);
+    }
+
+    #[test]
+    fn test_install() {
+        // Other tests may have generated the table for 3x3 already, in which case installing fails.
+        let table = SolutionTable::from_bytes(&SolutionTable::generate(3).to_bytes()).unwrap();
+        let _ = table.install();
+
+        assert_eq!(
+            SolutionTable::generate(3)
+                .install()
+                .err()
+                .map(|table| table.size()),
+            Some(3)
+        );
+        assert_eq!(
+            solve(&Board::new(3), Color::Black).unwrap().winner,
+            Color::Black
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let table = SolutionTable::generate(2);
+        let bytes = table.to_bytes();
+
+        assert_eq!(bytes.len(), 1 + 11); // 81 bits
+        assert_eq!(SolutionTable::from_bytes(&bytes).unwrap(), table);
+        assert_eq!(
+            SolutionTable::from_bytes(&bytes[..5]).err().unwrap(),
+            LoadError::InvalidLength(12, 5)
+        );
+        assert_eq!(
+            SolutionTable::from_bytes(&[5]).err().unwrap(),
+            LoadError::InvalidBoard(InvalidBoard::SizeOutOfBounds(5, 2, 4))
+        );
+    }
+}
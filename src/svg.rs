@@ -0,0 +1,558 @@
+use crate::board::Board;
+use crate::color::Color;
+use crate::coords::{to_column_char, CoordValue, Coords};
+use crate::edges::{get_edges_of_color, Edge};
+use crate::game::Game;
+use crate::moves::Move;
+use std::fmt::Write;
+use std::time::Duration;
+
+const EMPTY_FILL: &str = "#f2d9a6";
+const CELL_STROKE: &str = "#8c6d3f";
+const ANNOTATION_COLOR: &str = "#d00000";
+const SQRT_3: f64 = 1.732_050_807_568_877_2;
+
+/// A marker that can be drawn on a cell of an SVG diagram (see `SvgOptions`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Marker {
+    Dot,
+    Cross,
+    Triangle,
+    /// A short text, e.g. "A" to refer to the cell in the text accompanying a diagram.
+    Text(String),
+}
+
+/// Options for drawing boards and games as SVG diagrams, see `Board::to_svg` and `Game::to_svg`.
+///
+/// ```
+/// # use hexgame::{Board, Color, Coords, Marker, SvgOptions};
+/// let mut board = Board::new(5);
+/// board.play(Coords::new(2, 2), Color::Black).unwrap();
+///
+/// let options = SvgOptions {
+///     markers: vec![(Coords::new(1, 3), Marker::Text("A".to_string()))],
+///     arrows: vec![(Coords::new(2, 2), Coords::new(0, 3))],
+///     ..SvgOptions::default()
+/// };
+/// let svg = board.to_svg(&options);
+/// assert!(svg.starts_with("<svg"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// Distance from the center of a cell to its corners in pixels.
+    pub cell_radius: f64,
+    /// Draw letters and numbers around the board like `Display` does.
+    pub labels: bool,
+    /// Number the stones in the order they were played. This only has an effect for games.
+    pub move_numbers: bool,
+    /// Markers to draw on cells.
+    pub markers: Vec<(Coords, Marker)>,
+    /// Arrows to draw from one cell to another.
+    pub arrows: Vec<(Coords, Coords)>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_radius: 20.0,
+            labels: true,
+            move_numbers: false,
+            markers: vec![],
+            arrows: vec![],
+        }
+    }
+}
+
+pub fn board_to_svg(board: &Board, options: &SvgOptions) -> String {
+    let mut body = String::new();
+    write_position(&mut body, board, &[], options);
+    document(board.size(), options, &body)
+}
+
+pub fn game_to_svg(game: &Game, options: &SvgOptions) -> String {
+    let numbers = move_numbers(game.get_moves(), options);
+    let mut body = String::new();
+    write_position(&mut body, game.get_board(), &numbers, options);
+    document(game.get_board().size(), options, &body)
+}
+
+pub fn game_to_svg_frames(game: &Game, options: &SvgOptions) -> Vec<String> {
+    replay(game)
+        .iter()
+        .enumerate()
+        .map(|(move_count, board)| {
+            let numbers = move_numbers(&game.get_moves()[..move_count], options);
+            let mut body = String::new();
+            write_position(&mut body, board, &numbers, options);
+            document(board.size(), options, &body)
+        })
+        .collect()
+}
+
+pub fn game_to_animated_svg(game: &Game, options: &SvgOptions, frame_duration: Duration) -> String {
+    let boards = replay(game);
+    let seconds = frame_duration.as_secs_f64();
+    let mut body = String::new();
+
+    for (move_count, board) in boards.iter().enumerate() {
+        // Each frame is visible for `frame_duration`, the last frame remains visible.
+        let duration = if move_count + 1 < boards.len() {
+            format!("dur=\"{}s\"", seconds)
+        } else {
+            "fill=\"freeze\"".to_string()
+        };
+        writeln!(
+            body,
+            "<g visibility=\"hidden\"><set attributeName=\"visibility\" to=\"visible\" begin=\"{}s\" {}/>",
+            seconds * move_count as f64,
+            duration
+        )
+        .unwrap();
+        let numbers = move_numbers(&game.get_moves()[..move_count], options);
+        write_position(&mut body, board, &numbers, options);
+        body.push_str("</g>\n");
+    }
+
+    document(game.get_board().size(), options, &body)
+}
+
+/// Return the boards before the first move, after the first move, etc.
+fn replay(game: &Game) -> Vec<Board> {
+    let mut replayed_game = game.initial_position();
+    let mut boards = vec![replayed_game.get_board().clone()];
+    for mv in game.get_moves() {
+        replayed_game
+            .play_move(*mv)
+            .expect("Moves of a game can be replayed");
+        boards.push(replayed_game.get_board().clone());
+    }
+    boards
+}
+
+/// Return the cells that should be numbered. A swapped stone is numbered with the number of the swap move.
+fn move_numbers(moves: &[Move], options: &SvgOptions) -> Vec<(Coords, usize)> {
+    let mut numbers: Vec<(Coords, usize)> = vec![];
+    if !options.move_numbers {
+        return numbers;
+    }
+
+    for (index, mv) in moves.iter().enumerate() {
+        match mv {
+            Move::Place(coords) => numbers.push((*coords, index + 1)),
+            Move::Swap => {
+                for (coords, number) in numbers.iter_mut() {
+                    *coords = coords.transpose();
+                    *number = index + 1;
+                }
+            }
+        }
+    }
+    numbers
+}
+
+fn document(size: CoordValue, options: &SvgOptions, body: &str) -> String {
+    let geometry = Geometry::new(size, options.cell_radius);
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.1}\" height=\"{:.1}\" viewBox=\"0 0 {:.1} {:.1}\">",
+        geometry.width(),
+        geometry.height(),
+        geometry.width(),
+        geometry.height()
+    )
+    .unwrap();
+    if !options.arrows.is_empty() {
+        writeln!(
+            svg,
+            "<defs><marker id=\"arrowhead\" viewBox=\"0 0 10 10\" refX=\"8\" refY=\"5\" markerWidth=\"4\" markerHeight=\"4\" orient=\"auto\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"{}\"/></marker></defs>",
+            ANNOTATION_COLOR
+        )
+        .unwrap();
+    }
+    svg.push_str(body);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_position(
+    svg: &mut String,
+    board: &Board,
+    numbers: &[(Coords, usize)],
+    options: &SvgOptions,
+) {
+    let geometry = Geometry::new(board.size(), options.cell_radius);
+
+    for row in 0..board.size() {
+        for column in 0..board.size() {
+            let fill = match board.get_color(Coords { row, column }) {
+                Some(Color::Black) => "#000000",
+                Some(Color::White) => "#ffffff",
+                None => EMPTY_FILL,
+            };
+            writeln!(
+                svg,
+                "<polygon points=\"{}\" fill=\"{}\" stroke=\"{}\" stroke-width=\"1\"/>",
+                geometry.hexagon_points(row as f64, column as f64),
+                fill,
+                CELL_STROKE
+            )
+            .unwrap();
+        }
+    }
+
+    write_edges(svg, &geometry);
+
+    if options.labels {
+        write_labels(svg, &geometry);
+    }
+
+    for (coords, number) in numbers {
+        let color = match board.get_color(*coords) {
+            Some(Color::Black) => "#ffffff",
+            _ => "#000000",
+        };
+        write_text(svg, &geometry, *coords, &number.to_string(), color);
+    }
+
+    for (coords, marker) in &options.markers {
+        write_marker(svg, &geometry, *coords, marker);
+    }
+
+    for (from, to) in &options.arrows {
+        write_arrow(svg, &geometry, *from, *to);
+    }
+}
+
+fn write_edges(svg: &mut String, geometry: &Geometry) {
+    let width = geometry.radius / 4.0;
+    for color in [Color::Black, Color::White] {
+        let stroke = match color {
+            Color::Black => "#000000",
+            Color::White => "#ffffff",
+        };
+        for edge in get_edges_of_color(color) {
+            let points = geometry.edge_points(edge);
+            // An outline makes white edges visible on light backgrounds.
+            writeln!(
+                svg,
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"#000000\" stroke-width=\"{:.1}\" stroke-linecap=\"round\"/>",
+                points,
+                width + 2.0
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{:.1}\" stroke-linecap=\"round\"/>",
+                points, stroke, width
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn write_labels(svg: &mut String, geometry: &Geometry) {
+    let size = geometry.size as f64;
+    for column in 0..geometry.size {
+        let label = to_column_char(column).to_string();
+        for row in [-1.0, size] {
+            let (x, y) = geometry.center(row, column as f64);
+            write_label(svg, geometry, x, y, &label, "#000000");
+        }
+    }
+    for row in 0..geometry.size {
+        let label = (row + 1).to_string();
+        for column in [-1.0, size] {
+            let (x, y) = geometry.center(row as f64, column);
+            write_label(svg, geometry, x, y, &label, "#000000");
+        }
+    }
+}
+
+fn write_text(svg: &mut String, geometry: &Geometry, coords: Coords, text: &str, color: &str) {
+    let (x, y) = geometry.center(coords.row as f64, coords.column as f64);
+    write_label(svg, geometry, x, y, text, color);
+}
+
+fn write_label(svg: &mut String, geometry: &Geometry, x: f64, y: f64, text: &str, color: &str) {
+    writeln!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\">{}</text>",
+        x,
+        y,
+        geometry.radius * 0.8,
+        color,
+        escape(text)
+    )
+    .unwrap();
+}
+
+fn write_marker(svg: &mut String, geometry: &Geometry, coords: Coords, marker: &Marker) {
+    let (x, y) = geometry.center(coords.row as f64, coords.column as f64);
+    let r = geometry.radius * 0.4;
+    match marker {
+        Marker::Dot => writeln!(
+            svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\"/>",
+            x,
+            y,
+            r / 2.0,
+            ANNOTATION_COLOR
+        ),
+        Marker::Cross => writeln!(
+            svg,
+            "<path d=\"M {:.1} {:.1} L {:.1} {:.1} M {:.1} {:.1} L {:.1} {:.1}\" stroke=\"{}\" stroke-width=\"{:.1}\"/>",
+            x - r,
+            y - r,
+            x + r,
+            y + r,
+            x - r,
+            y + r,
+            x + r,
+            y - r,
+            ANNOTATION_COLOR,
+            geometry.radius / 8.0
+        ),
+        Marker::Triangle => writeln!(
+            svg,
+            "<polygon points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{:.1}\"/>",
+            x,
+            y - r,
+            x + r * SQRT_3 / 2.0,
+            y + r / 2.0,
+            x - r * SQRT_3 / 2.0,
+            y + r / 2.0,
+            ANNOTATION_COLOR,
+            geometry.radius / 8.0
+        ),
+        Marker::Text(text) => {
+            write_text(svg, geometry, coords, text, ANNOTATION_COLOR);
+            Ok(())
+        }
+    }
+    .unwrap();
+}
+
+fn write_arrow(svg: &mut String, geometry: &Geometry, from: Coords, to: Coords) {
+    let (x1, y1) = geometry.center(from.row as f64, from.column as f64);
+    let (x2, y2) = geometry.center(to.row as f64, to.column as f64);
+    writeln!(
+        svg,
+        "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" stroke-width=\"{:.1}\" marker-end=\"url(#arrowhead)\"/>",
+        x1,
+        y1,
+        x2,
+        y2,
+        ANNOTATION_COLOR,
+        geometry.radius / 6.0
+    )
+    .unwrap();
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Positions of the hexagons in the slanted layout used by `Display`, with pointy-topped hexagons.
+/// Rows and columns are floats so that labels can be placed at row/column -1 and `size`.
+struct Geometry {
+    size: CoordValue,
+    radius: f64,
+}
+
+impl Geometry {
+    fn new(size: CoordValue, radius: f64) -> Self {
+        Self { size, radius }
+    }
+
+    fn cell_width(&self) -> f64 {
+        SQRT_3 * self.radius
+    }
+
+    fn width(&self) -> f64 {
+        self.cell_width() * (1.5 * self.size as f64 + 2.0)
+    }
+
+    fn height(&self) -> f64 {
+        self.radius * (1.5 * self.size as f64 + 3.5)
+    }
+
+    fn center(&self, row: f64, column: f64) -> (f64, f64) {
+        (
+            self.cell_width() * (column + row / 2.0 + 1.5),
+            self.radius * (1.5 * row + 2.5),
+        )
+    }
+
+    /// Corner of a hexagon, counted clockwise starting with the top corner.
+    fn corner(&self, row: f64, column: f64, index: usize) -> (f64, f64) {
+        let (x, y) = self.center(row, column);
+        let angle = (60.0 * index as f64 - 90.0).to_radians();
+        (x + self.radius * angle.cos(), y + self.radius * angle.sin())
+    }
+
+    fn hexagon_points(&self, row: f64, column: f64) -> String {
+        format_points((0..6).map(|index| self.corner(row, column, index)))
+    }
+
+    fn edge_points(&self, edge: Edge) -> String {
+        const TOP: usize = 0;
+        const UPPER_RIGHT: usize = 1;
+        const LOWER_RIGHT: usize = 2;
+        const BOTTOM: usize = 3;
+        const LOWER_LEFT: usize = 4;
+        const UPPER_LEFT: usize = 5;
+
+        let last = (self.size - 1) as f64;
+        let cells = (0..self.size).map(|i| i as f64);
+        let points: Vec<(f64, f64)> = match edge {
+            Edge::Top => cells
+                .flat_map(|column| [UPPER_LEFT, TOP].map(|corner| self.corner(0.0, column, corner)))
+                .chain([self.corner(0.0, last, UPPER_RIGHT)])
+                .collect(),
+            Edge::Bottom => cells
+                .flat_map(|column| {
+                    [LOWER_LEFT, BOTTOM].map(|corner| self.corner(last, column, corner))
+                })
+                .chain([self.corner(last, last, LOWER_RIGHT)])
+                .collect(),
+            Edge::Left => cells
+                .flat_map(|row| {
+                    [UPPER_LEFT, LOWER_LEFT].map(|corner| self.corner(row, 0.0, corner))
+                })
+                .collect(),
+            Edge::Right => cells
+                .flat_map(|row| {
+                    [UPPER_RIGHT, LOWER_RIGHT].map(|corner| self.corner(row, last, corner))
+                })
+                .collect(),
+        };
+        format_points(points.into_iter())
+    }
+}
+
+fn format_points(points: impl Iterator<Item = (f64, f64)>) -> String {
+    points
+        .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_to_svg() {
+        let mut board = Board::new(3);
+        board.play(Coords::new(0, 0), Color::Black).unwrap();
+        board.play(Coords::new(1, 2), Color::White).unwrap();
+
+        let svg = board_to_svg(&board, &SvgOptions::default());
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<polygon").count(), 9);
+        assert_eq!(svg.matches("fill=\"#000000\" stroke").count(), 1);
+        assert_eq!(svg.matches("fill=\"#ffffff\" stroke").count(), 1);
+        // Two polylines (outline and color) for each of the four edges.
+        assert_eq!(svg.matches("<polyline").count(), 8);
+        // Letters and numbers on both sides.
+        assert_eq!(svg.matches("<text").count(), 12);
+    }
+
+    #[test]
+    fn test_geometry() {
+        let geometry = Geometry::new(2, 10.0);
+        let (x, y) = geometry.center(0.0, 0.0);
+        let (right_x, right_y) = geometry.center(0.0, 1.0);
+        let (below_x, below_y) = geometry.center(1.0, 0.0);
+
+        assert!((right_x - x - 10.0 * SQRT_3).abs() < 1e-9);
+        assert_eq!(right_y, y);
+        // The next row is shifted by half a cell (slanted layout).
+        assert!((below_x - x - 5.0 * SQRT_3).abs() < 1e-9);
+        assert_eq!(below_y - y, 15.0);
+        // Neighboring hexagons share corners.
+        let bottom = geometry.corner(0.0, 0.0, 3);
+        let upper_left = geometry.corner(1.0, 0.0, 5);
+        assert!((bottom.0 - upper_left.0).abs() < 1e-9 && (bottom.1 - upper_left.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_markers_and_arrows() {
+        let board = Board::new(3);
+        let options = SvgOptions {
+            labels: false,
+            markers: vec![
+                (Coords::new(0, 0), Marker::Dot),
+                (Coords::new(0, 1), Marker::Cross),
+                (Coords::new(0, 2), Marker::Triangle),
+                (Coords::new(1, 0), Marker::Text("<A>".to_string())),
+            ],
+            arrows: vec![(Coords::new(2, 2), Coords::new(1, 1))],
+            ..SvgOptions::default()
+        };
+
+        let svg = board_to_svg(&board, &options);
+
+        assert!(svg.contains("<circle"));
+        assert!(svg.contains("<path d=\"M"));
+        assert!(svg.contains("&lt;A&gt;</text>"));
+        assert!(svg.contains("<marker id=\"arrowhead\""));
+        assert!(svg.contains("marker-end=\"url(#arrowhead)\""));
+        assert_eq!(svg.matches("<polygon").count(), 10);
+    }
+
+    #[test]
+    fn test_move_numbers() {
+        let moves = [
+            Move::Place(Coords::new(0, 1)),
+            Move::Swap,
+            Move::Place(Coords::new(2, 2)),
+        ];
+        let options = SvgOptions {
+            move_numbers: true,
+            ..SvgOptions::default()
+        };
+
+        assert_eq!(
+            move_numbers(&moves, &options),
+            vec![(Coords::new(1, 0), 2), (Coords::new(2, 2), 3)]
+        );
+        assert!(move_numbers(&moves, &SvgOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_game_to_svg_frames() {
+        let mut game = Game::new(3);
+        game.play(Coords::new(1, 1)).unwrap();
+        game.play(Coords::new(0, 2)).unwrap();
+        let options = SvgOptions {
+            labels: false,
+            move_numbers: true,
+            ..SvgOptions::default()
+        };
+
+        let frames = game_to_svg_frames(&game, &options);
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].matches("<text").count(), 0);
+        assert_eq!(frames[1].matches("<text").count(), 1);
+        assert_eq!(frames[2], game_to_svg(&game, &options));
+    }
+
+    #[test]
+    fn test_animated_svg() {
+        let mut game = Game::new(3);
+        game.play(Coords::new(1, 1)).unwrap();
+
+        let svg = game_to_animated_svg(&game, &SvgOptions::default(), Duration::from_millis(500));
+
+        assert_eq!(svg.matches("<svg").count(), 1);
+        assert!(svg.contains("begin=\"0s\" dur=\"0.5s\""));
+        assert!(svg.contains("begin=\"0.5s\" fill=\"freeze\""));
+    }
+}
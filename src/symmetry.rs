@@ -0,0 +1,210 @@
+use crate::board::{Board, StoneMatrix};
+use crate::color::Color;
+use crate::coords::{CoordValue, Coords};
+
+/// The symmetries of a Hex board.
+///
+/// Rotating a board by 180° yields an equivalent position. Reflecting a board at the long diagonal
+/// (see `Coords::transpose`) exchanges the edges of Black and White, so the position is only equivalent if the colors
+/// of all stones (and the player to move) are swapped as well. Combining both yields the reflection at the short diagonal.
+///
+/// All symmetries are their own inverse.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    /// Rotation by 180°.
+    Rotate180,
+    /// Reflection at the long diagonal (from a1 to the opposite corner), swapping colors.
+    TransposeSwapColors,
+    /// Reflection at the short diagonal, swapping colors.
+    Rotate180TransposeSwapColors,
+}
+
+impl Symmetry {
+    /// All symmetries, starting with `Identity`.
+    pub const ALL: [Symmetry; 4] = [
+        Symmetry::Identity,
+        Symmetry::Rotate180,
+        Symmetry::TransposeSwapColors,
+        Symmetry::Rotate180TransposeSwapColors,
+    ];
+
+    /// Return whether this symmetry swaps the colors of stones and players.
+    pub fn swaps_colors(&self) -> bool {
+        matches!(
+            self,
+            Symmetry::TransposeSwapColors | Symmetry::Rotate180TransposeSwapColors
+        )
+    }
+
+    /// Return the coordinates of the given cell after applying this symmetry to a board of the given size.
+    pub fn apply_to_coords(&self, coords: Coords, size: CoordValue) -> Coords {
+        match self {
+            Symmetry::Identity => coords,
+            Symmetry::Rotate180 => coords.rotate_180(size),
+            Symmetry::TransposeSwapColors => coords.transpose(),
+            Symmetry::Rotate180TransposeSwapColors => coords.rotate_180(size).transpose(),
+        }
+    }
+
+    /// Return the given color (e.g. the player to move) after applying this symmetry.
+    pub fn apply_to_color(&self, color: Color) -> Color {
+        if self.swaps_colors() {
+            color.opponent_color()
+        } else {
+            color
+        }
+    }
+
+    /// Return a copy of the given board after applying this symmetry.
+    pub fn apply_to_board(&self, board: &Board) -> Board {
+        let size = board.size();
+        let mut stones: StoneMatrix = vec![vec![None; size as usize]; size as usize];
+        for row in 0..size {
+            for column in 0..size {
+                let coords = Coords { row, column };
+                let target = self.apply_to_coords(coords, size);
+                stones[target.row as usize][target.column as usize] = board
+                    .get_color(coords)
+                    .map(|color| self.apply_to_color(color));
+            }
+        }
+        Board::from_stone_matrix(stones).expect("Transformed boards have the same size")
+    }
+}
+
+/// Return the lexicographically smallest board among all symmetric boards and the symmetry that produces it.
+///
+/// Cells are compared row by row, where empty cells are smaller than black stones, which are smaller than white stones.
+pub fn canonicalize(board: &Board) -> (Board, Symmetry) {
+    let size = board.size();
+    let key = |symmetry: Symmetry| -> Vec<u8> {
+        // Instead of transforming the board, read the cells in the order given by the symmetry.
+        let mut key = Vec::with_capacity(size as usize * size as usize);
+        for row in 0..size {
+            for column in 0..size {
+                let source = symmetry.apply_to_coords(Coords { row, column }, size);
+                key.push(
+                    match board
+                        .get_color(source)
+                        .map(|color| symmetry.apply_to_color(color))
+                    {
+                        None => 0,
+                        Some(Color::Black) => 1,
+                        Some(Color::White) => 2,
+                    },
+                );
+            }
+        }
+        key
+    };
+
+    let symmetry = Symmetry::ALL
+        .into_iter()
+        .min_by_key(|symmetry| key(*symmetry))
+        .unwrap();
+    (symmetry.apply_to_board(board), symmetry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edges::get_edges_of_color;
+
+    fn has_won(board: &Board, color: Color) -> bool {
+        let [edge, opposite_edge] = get_edges_of_color(color);
+        board.is_in_same_set(edge, opposite_edge)
+    }
+
+    fn board_with_stones(size: CoordValue, black: &[Coords], white: &[Coords]) -> Board {
+        let mut board = Board::new(size);
+        for coords in black {
+            board.play(*coords, Color::Black).unwrap();
+        }
+        for coords in white {
+            board.play(*coords, Color::White).unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn test_rotate_180() {
+        let board = board_with_stones(3, &[Coords::new(0, 1)], &[Coords::new(1, 0)]);
+        let rotated = Symmetry::Rotate180.apply_to_board(&board);
+
+        assert_eq!(
+            rotated.to_stone_matrix(),
+            board_with_stones(3, &[Coords::new(2, 1)], &[Coords::new(1, 2)]).to_stone_matrix()
+        );
+    }
+
+    #[test]
+    fn test_transpose_swap_colors() {
+        let board = board_with_stones(3, &[Coords::new(0, 1)], &[Coords::new(2, 0)]);
+        let transposed = Symmetry::TransposeSwapColors.apply_to_board(&board);
+
+        assert_eq!(
+            transposed.to_stone_matrix(),
+            board_with_stones(3, &[Coords::new(0, 2)], &[Coords::new(1, 0)]).to_stone_matrix()
+        );
+    }
+
+    #[test]
+    fn test_symmetries_are_involutions() {
+        let board = board_with_stones(
+            4,
+            &[Coords::new(0, 1), Coords::new(3, 2)],
+            &[Coords::new(1, 3)],
+        );
+        for symmetry in Symmetry::ALL {
+            let twice = symmetry.apply_to_board(&symmetry.apply_to_board(&board));
+            assert_eq!(twice.to_stone_matrix(), board.to_stone_matrix());
+        }
+    }
+
+    #[test]
+    fn test_symmetries_preserve_winner() {
+        // Black connects top and bottom, so White connects left and right after swapping colors.
+        let board = board_with_stones(2, &[Coords::new(0, 1), Coords::new(1, 0)], &[]);
+        assert!(has_won(&board, Color::Black));
+
+        for symmetry in Symmetry::ALL {
+            let transformed = symmetry.apply_to_board(&board);
+            assert!(has_won(&transformed, symmetry.apply_to_color(Color::Black)));
+        }
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        // All four equivalent positions have the same canonical form.
+        let board = board_with_stones(3, &[Coords::new(0, 1)], &[Coords::new(2, 2)]);
+        let (canonical, _) = canonicalize(&board);
+
+        for symmetry in Symmetry::ALL {
+            let transformed = symmetry.apply_to_board(&board);
+            let (other_canonical, other_symmetry) = canonicalize(&transformed);
+            assert_eq!(
+                other_canonical.to_stone_matrix(),
+                canonical.to_stone_matrix()
+            );
+            assert_eq!(
+                other_symmetry
+                    .apply_to_board(&transformed)
+                    .to_stone_matrix(),
+                canonical.to_stone_matrix()
+            );
+        }
+
+        // Only the reflection at the long diagonal leaves the first row empty.
+        assert_eq!(
+            canonical.to_stone_matrix(),
+            board_with_stones(3, &[Coords::new(2, 2)], &[Coords::new(1, 0)]).to_stone_matrix()
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_empty_board() {
+        let (_, symmetry) = canonicalize(&Board::new(5));
+        assert_eq!(symmetry, Symmetry::Identity);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::color::Color;
+use crate::coords::Coords;
+use crate::edges::get_edges_of_color;
+use crate::hex_cells::{HexCells, Index};
+use crate::neighbors::get_neighbors;
+use crate::union_find::UnionFind;
+
+/// An empty cell together with the sets (represented by their roots) of the player's stones and edges around it.
+struct Candidate {
+    index: Index,
+    roots: Vec<Index>,
+    touches_first_edge: bool,
+    touches_second_edge: bool,
+}
+
+impl Candidate {
+    fn is_winning(&self) -> bool {
+        self.touches_first_edge && self.touches_second_edge
+    }
+}
+
+/// Return all empty cells with the sets of the player's stones around them.
+/// Return None if the player has already connected their edges.
+fn find_candidates(cells: &HexCells, color: Color) -> Option<Vec<Candidate>> {
+    let [first_edge, second_edge] = get_edges_of_color(color);
+    let first_root = cells.find_root(cells.index_from_edge(first_edge));
+    let second_root = cells.find_root(cells.index_from_edge(second_edge));
+    if first_root == second_root {
+        return None;
+    }
+
+    let cell_count = cells.size as Index * cells.size as Index;
+    let candidates = (0..cell_count)
+        .filter(|index| cells.get_color_at_index(*index).is_none())
+        .map(|index| {
+            let mut roots: Vec<Index> = get_neighbors(cells, index)
+                .filter(|neighbor| cells.get_color_at_index(*neighbor) == Some(color))
+                .map(|neighbor| cells.find_root(neighbor))
+                .collect();
+            roots.sort_unstable();
+            roots.dedup();
+            Candidate {
+                index,
+                touches_first_edge: roots.contains(&first_root),
+                touches_second_edge: roots.contains(&second_root),
+                roots,
+            }
+        })
+        .collect();
+    Some(candidates)
+}
+
+pub fn winning_moves(cells: &HexCells, color: Color) -> Vec<Coords> {
+    match find_candidates(cells, color) {
+        None => vec![],
+        Some(candidates) => candidates
+            .iter()
+            .filter(|candidate| candidate.is_winning())
+            .map(|candidate| cells.coords_from_index(candidate.index))
+            .collect(),
+    }
+}
+
+pub fn threats(cells: &HexCells, color: Color) -> Vec<Coords> {
+    let candidates = match find_candidates(cells, color) {
+        None => return vec![],
+        Some(candidates) => candidates,
+    };
+
+    // A move creates a new winning move if it is connected to one edge and there is an empty cell connected to the
+    // other edge that is either adjacent to the move or to one of the sets adjacent to the move.
+    let creates_win = |candidate: &Candidate, others: &[&Candidate]| {
+        others.iter().any(|other| {
+            get_neighbors(cells, candidate.index).any(|neighbor| neighbor == other.index)
+                || other
+                    .roots
+                    .iter()
+                    .any(|root| candidate.roots.contains(root))
+        })
+    };
+    let touching_first: Vec<&Candidate> = candidates
+        .iter()
+        .filter(|candidate| candidate.touches_first_edge && !candidate.is_winning())
+        .collect();
+    let touching_second: Vec<&Candidate> = candidates
+        .iter()
+        .filter(|candidate| candidate.touches_second_edge && !candidate.is_winning())
+        .collect();
+
+    candidates
+        .iter()
+        .filter(|candidate| !candidate.is_winning())
+        .filter(|candidate| {
+            (candidate.touches_first_edge && creates_win(candidate, &touching_second))
+                || (candidate.touches_second_edge && creates_win(candidate, &touching_first))
+        })
+        .map(|candidate| cells.coords_from_index(candidate.index))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::color::Color;
+    use crate::coords::Coords;
+    use crate::edges::get_edges_of_color;
+
+    fn coords(names: &[&str]) -> Vec<Coords> {
+        names.iter().map(|name| name.parse().unwrap()).collect()
+    }
+
+    /// Compute the winning moves and threats by trying all moves.
+    fn brute_force(board: &Board, color: Color) -> (Vec<Coords>, Vec<Coords>) {
+        let wins = |board: &Board, coords: Coords| {
+            let mut board = board.clone();
+            board.play(coords, color).unwrap();
+            let [first_edge, second_edge] = get_edges_of_color(color);
+            board.is_in_same_set(first_edge, second_edge)
+        };
+        let winning_moves: Vec<Coords> = board
+            .get_empty_cells()
+            .into_iter()
+            .filter(|coords| wins(board, *coords))
+            .collect();
+        let threats = board
+            .get_empty_cells()
+            .into_iter()
+            .filter(|coords| !winning_moves.contains(coords))
+            .filter(|coords| {
+                let mut next = board.clone();
+                next.play(*coords, color).unwrap();
+                next.get_empty_cells()
+                    .into_iter()
+                    .any(|other| !winning_moves.contains(&other) && wins(&next, other))
+            })
+            .collect();
+        (winning_moves, threats)
+    }
+
+    #[test]
+    fn test_winning_moves() {
+        let board: Board = "
+            .  .  ●
+            ○  ○  ●
+            .  .  .
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(board.winning_moves(Color::Black), coords(&["b3", "c3"]));
+        assert!(board.winning_moves(Color::White).is_empty());
+    }
+
+    #[test]
+    fn test_threats() {
+        let board: Board = "
+            .  .  .
+            .  ●  .
+            .  .  .
+        "
+        .parse()
+        .unwrap();
+
+        // Black connects b2 to one edge and then threatens to connect it to the other one.
+        assert!(board.winning_moves(Color::Black).is_empty());
+        assert_eq!(
+            board.threats(Color::Black),
+            coords(&["b1", "c1", "a3", "b3"])
+        );
+        assert!(board.threats(Color::White).is_empty());
+    }
+
+    #[test]
+    fn test_finished_board() {
+        let board: Board = "
+            ●  .
+            ●  .
+        "
+        .parse()
+        .unwrap();
+
+        assert!(board.winning_moves(Color::Black).is_empty());
+        assert!(board.threats(Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_compare_with_brute_force() {
+        let boards = [
+            "
+                .  ●  .  .
+                .  ○  ●  .
+                ○  .  .  .
+                .  .  ●  .
+            ",
+            "
+                ○  .  .  ●
+                .  ●  .  .
+                .  .  ○  .
+                ●  .  .  ○
+            ",
+            "
+                .  .  ○  .  .
+                .  ●  .  ●  .
+                .  ○  .  .  .
+                ●  .  ●  ○  .
+                .  .  .  .  .
+            ",
+        ];
+        for string in boards {
+            let board: Board = string.parse().unwrap();
+            for color in [Color::Black, Color::White] {
+                assert_eq!(
+                    (board.winning_moves(color), board.threats(color)),
+                    brute_force(&board, color),
+                    "{} {:?}",
+                    string,
+                    color
+                );
+            }
+        }
+    }
+}
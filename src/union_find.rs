@@ -9,7 +9,6 @@ use std::cmp::Ordering;
 /// It is also used by Mohex: https://github.com/cgao3/benzene-vanilla-cmake/blob/master/src/mohex/MoHexBoard.cpp
 ///
 /// This trait makes no assumptions on how parents are stored. Implementors must provide `get_parent` and `set_parent` methods for this.
-
 pub trait UnionFind<T: Copy + PartialOrd + Eq> {
     fn get_parent(&self, item: T) -> Option<T>;
     // Note that this method does not require `&mut self`: